@@ -0,0 +1,606 @@
+//! ALU chip: receives `Bus::Alu`'s `(opcode, alu_op, rs1_val, rs2_val, rd_val,
+//! nonce)` tuple and independently recomputes `alu_op(rs1_val, rs2_val)` to
+//! bind it to `rd_val`, closing the gap `chips::cpu::air::CpuChip::eval`'s
+//! own comment used to flag: `CpuChip` only ever copies `alu_result` into
+//! `rd_val`, it never recomputes the operation, so without this chip a
+//! prover could claim any `rd_val` at all for an ADD/AND/SLT/etc row.
+//!
+//! Row-aligned 1:1 with `chips::cpu::trace::generate_cpu_trace`'s trace
+//! (same `trace_len`, same per-row `nonce = row index`), with `is_real`
+//! marking the rows that are actually ALU/ALU-immediate ops -- the same
+//! "sparse subset of a row-aligned companion trace" shape
+//! `chips::register::RegisterColumns::is_real` uses, chosen because ALU
+//! operand values span the full 32-bit domain rather than some small fixed
+//! table a `RangeCheckChip`-style enumeration could cover.
+//!
+//! ADD/SUB are proven via a witnessed carry/borrow bit against the operands'
+//! bit decompositions (needed for the sign bits SLT/SLTU use); AND/OR/XOR
+//! reuse the per-bit field-arithmetic identities `chips::syscall::keccak`
+//! documents (`AND(a,b) = a*b`, `OR(a,b) = a+b-ab`, `XOR(a,b) = a+b-2ab`);
+//! SLTU is just SUB's borrow bit, and SLT is that borrow bit corrected for
+//! the operands' signs (`slt = sltu XOR sign(rs1) XOR sign(rs2)`), so all
+//! three share one `(diff, diff_borrow)` witness pair. ADD and SUB's results
+//! are range-checked through `Bus::RangeCheck16` exactly like
+//! `chips::cpu`'s shift-overflow/remainder limbs (`sends`/
+//! `populate_range_bus_columns` there is the template for `sends` and
+//! `machine::check_range_bus_closure` below).
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_from_base, ext_inverse, ext_mul, ext_one, fingerprint_n};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::chips::range::{self, decompose_u32, RANGE_CHECK_BITS};
+use crate::trace::ExecutionTrace;
+
+/// Number of bits an operand is decomposed into.
+const ALU_OPERAND_BITS: usize = 32;
+
+/// ALU operation codes this chip recomputes, matching
+/// `chips::cpu::columns::AluOp`'s discriminants for the subset `CpuChip`
+/// actually routes over `Bus::Alu` (shift ops go to `ShiftPowChip` instead,
+/// and RV32M/RV32F ops aren't decoded yet -- see `decode_alu_op` below).
+/// Duplicated rather than shared across the chip boundary, the same way
+/// `RegisterChip` independently re-derives its access log instead of
+/// trusting CPU-side columns.
+mod op {
+    pub const ADD: u8 = 0;
+    pub const SUB: u8 = 1;
+    pub const AND: u8 = 2;
+    pub const OR: u8 = 3;
+    pub const XOR: u8 = 4;
+    pub const SLT: u8 = 8;
+    pub const SLTU: u8 = 9;
+}
+
+/// ALU trace columns: one row per CPU row (see module doc comment).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AluColumns<T> {
+    // === Bus tuple (6 columns), mirrors `Bus::Alu`'s `(opcode, alu_op,
+    // rs1_val, rs2_val, rd_val, nonce)` ===
+    pub opcode: T,
+    pub alu_op: T,
+    pub rs1_val: T,
+    pub rs2_val: T,
+    pub rd_val: T,
+    pub nonce: T,
+
+    /// 1 for a genuine ALU/ALU-immediate row, 0 for every other CPU row
+    /// (including padding).
+    pub is_real: T,
+
+    // === Operation selectors (7 columns, one-hot, summing to `is_real`) ===
+    pub op_is_add: T,
+    pub op_is_sub: T,
+    pub op_is_and: T,
+    pub op_is_or: T,
+    pub op_is_xor: T,
+    pub op_is_slt: T,
+    pub op_is_sltu: T,
+
+    // === Operand bit decompositions (64 columns) ===
+    /// `rs1_val`'s bits, LSB first; `rs1_bits[31]` is its sign bit, used by
+    /// SLT's sign correction.
+    pub rs1_bits: [T; ALU_OPERAND_BITS],
+    /// `rs2_val`'s bits, LSB first; `rs2_bits[31]` is its sign bit.
+    pub rs2_bits: [T; ALU_OPERAND_BITS],
+
+    // === ADD witnesses (2 columns) ===
+    /// `rs1_val + rs2_val`, before truncating to 32 bits.
+    pub sum: T,
+    /// Carry bit out of `sum`: `rd_val = sum - sum_overflow * 2^32` when
+    /// `op_is_add`.
+    pub sum_overflow: T,
+
+    // === SUB/SLT/SLTU shared witnesses (2 columns) ===
+    /// `rs1_val - rs2_val` as an unsigned 32-bit value: `diff = rs1_val -
+    /// rs2_val + diff_borrow * 2^32`. Shared by SUB (`rd_val = diff`), SLTU
+    /// (`rd_val = diff_borrow`, the borrow bit itself), and SLT
+    /// (`rd_val = diff_borrow` corrected for the operands' signs).
+    pub diff: T,
+    /// Borrow bit out of `diff`: 1 iff `rs1_val < rs2_val` unsigned.
+    pub diff_borrow: T,
+
+    // === ADD/SUB result range-check limbs (2 columns) ===
+    /// Low 16 bits of `rd_val`, meaningful (and range-checked via
+    /// `Bus::RangeCheck16`) only when `op_is_add` or `op_is_sub` -- AND/OR/XOR
+    /// and SLT/SLTU already bound `rd_val` to a bit-combination or boolean
+    /// that's 32-bit valid by construction.
+    pub rd_lo: T,
+    /// High 16 bits of `rd_val`.
+    pub rd_hi: T,
+
+    // === Cross-chip ALU LogUp bus (receive side, 8 columns, see
+    // `machine::check_alu_bus_closure`) ===
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// over `(opcode, alu_op, rs1_val, rs2_val, rd_val, nonce)`.
+    pub f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `-is_real / fingerprint`
+    /// over this chip's rows -- the receive side of `Bus::Alu`.
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `chips::cpu::columns::CpuColumns::alu_bus_phi`.
+    pub phi: [T; 4],
+
+    // === Cross-chip range-check LogUp bus (send side, 12 columns), the ALU
+    // analogue of `chips::cpu::columns::CpuColumns::range_bus_phi` ===
+    /// Inverse, in the degree-4 extension, of this row's range-check-bus
+    /// fingerprint over `rd_lo`.
+    pub range_bus_f_inv_lo: [T; 4],
+    /// Inverse, in the degree-4 extension, of this row's range-check-bus
+    /// fingerprint over `rd_hi`.
+    pub range_bus_f_inv_hi: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` summed over this row's two limb touches -- the send side
+    /// of this chip's contribution to `Bus::RangeCheck16`, gated by
+    /// `op_is_add + op_is_sub`. `machine::ZkIrMachine` checks the closure
+    /// against `RangeCheckColumns::phi` (merged with every other sender's
+    /// tally, same as `chips::cpu`'s shift limbs).
+    pub range_bus_phi: [T; 4],
+}
+
+/// Number of columns in the ALU trace.
+pub const ALU_NUM_COLUMNS: usize = 6 + 1 + 7 + ALU_OPERAND_BITS * 2 + 2 + 2 + 2 + 4 + 4 + 4 + 4 + 4;
+
+impl<T> AluColumns<T> {
+    pub const NUM_COLUMNS: usize = ALU_NUM_COLUMNS;
+}
+
+impl<T> Borrow<AluColumns<T>> for [T; ALU_NUM_COLUMNS] {
+    fn borrow(&self) -> &AluColumns<T> {
+        unsafe { &*(self.as_ptr() as *const AluColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<AluColumns<T>> for [T; ALU_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut AluColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut AluColumns<T>) }
+    }
+}
+
+/// ALU chip: receives `Bus::Alu` and proves `rd_val == alu_op(rs1_val,
+/// rs2_val)` for ADD/SUB/AND/OR/XOR/SLT/SLTU.
+pub struct AluChip;
+
+impl Default for AluChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AluChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for AluChip {
+    fn width(&self) -> usize {
+        AluColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for AluChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let local_arr: &[AB::Var; ALU_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let local: &AluColumns<AB::Var> = local_arr.borrow();
+
+        builder.assert_zero(local.is_real.into() * (AB::Expr::ONE - local.is_real.into()));
+
+        // === Operation selectors: one-hot, summing to `is_real` ===
+        let selectors = [
+            local.op_is_add,
+            local.op_is_sub,
+            local.op_is_and,
+            local.op_is_or,
+            local.op_is_xor,
+            local.op_is_slt,
+            local.op_is_sltu,
+        ];
+        let mut selector_sum = AB::Expr::ZERO;
+        for s in selectors {
+            builder.assert_zero(s.into() * (AB::Expr::ONE - s.into()));
+            selector_sum = selector_sum + s.into();
+        }
+        builder.assert_eq(selector_sum, local.is_real.into());
+
+        let op_codes = [op::ADD, op::SUB, op::AND, op::OR, op::XOR, op::SLT, op::SLTU];
+        let mut alu_op_weighted = AB::Expr::ZERO;
+        for (s, code) in selectors.into_iter().zip(op_codes) {
+            alu_op_weighted = alu_op_weighted + s.into() * AB::Expr::from_canonical_u8(code);
+        }
+        builder.assert_eq(local.alu_op, alu_op_weighted);
+
+        // === Bit decompositions: boolean, reconstructing the operands ===
+        let mut rs1_reconstructed = AB::Expr::ZERO;
+        let mut rs2_reconstructed = AB::Expr::ZERO;
+        for i in 0..ALU_OPERAND_BITS {
+            builder.assert_zero(local.rs1_bits[i].into() * (AB::Expr::ONE - local.rs1_bits[i].into()));
+            builder.assert_zero(local.rs2_bits[i].into() * (AB::Expr::ONE - local.rs2_bits[i].into()));
+            let weight = AB::Expr::from_wrapped_u64(1u64 << i);
+            rs1_reconstructed = rs1_reconstructed + weight.clone() * local.rs1_bits[i].into();
+            rs2_reconstructed = rs2_reconstructed + weight * local.rs2_bits[i].into();
+        }
+        builder.assert_eq(local.rs1_val, rs1_reconstructed);
+        builder.assert_eq(local.rs2_val, rs2_reconstructed);
+
+        // === AND/OR/XOR via per-bit field arithmetic, see
+        // `chips::syscall::keccak`'s doc comment for the same identities ===
+        let bitwise_multiplicity: AB::Expr =
+            local.op_is_and.into() + local.op_is_or.into() + local.op_is_xor.into();
+        let mut rd_bitwise = AB::Expr::ZERO;
+        for i in 0..ALU_OPERAND_BITS {
+            let a = local.rs1_bits[i].into();
+            let b = local.rs2_bits[i].into();
+            let and_bit = a.clone() * b.clone();
+            let or_bit = a.clone() + b.clone() - and_bit.clone();
+            let xor_bit = a + b - and_bit.clone() * AB::Expr::from_canonical_u32(2);
+            let bit = local.op_is_and.into() * and_bit
+                + local.op_is_or.into() * or_bit
+                + local.op_is_xor.into() * xor_bit;
+            rd_bitwise = rd_bitwise + AB::Expr::from_wrapped_u64(1u64 << i) * bit;
+        }
+        builder
+            .when(bitwise_multiplicity)
+            .assert_eq(local.rd_val, rd_bitwise);
+
+        // === ADD: `rd_val = sum - sum_overflow * 2^32`, range-checked via
+        // `rd_lo`/`rd_hi` ===
+        let two_pow_32 = AB::Expr::from_wrapped_u64(1u64 << 32);
+        let two_pow_16 = AB::Expr::from_canonical_u32(1 << RANGE_CHECK_BITS);
+        builder.assert_zero(local.sum_overflow.into() * (AB::Expr::ONE - local.sum_overflow.into()));
+        builder.assert_eq(local.sum, local.rs1_val.into() + local.rs2_val.into());
+        builder
+            .when(local.op_is_add)
+            .assert_eq(local.rd_val, local.sum.into() - local.sum_overflow.into() * two_pow_32.clone());
+
+        // === SUB/SLT/SLTU: shared `(diff, diff_borrow)` witness ===
+        builder.assert_zero(local.diff_borrow.into() * (AB::Expr::ONE - local.diff_borrow.into()));
+        let sub_family: AB::Expr = local.op_is_sub.into() + local.op_is_slt.into() + local.op_is_sltu.into();
+        builder.when(sub_family).assert_eq(
+            local.diff,
+            local.rs1_val.into() - local.rs2_val.into() + local.diff_borrow.into() * two_pow_32,
+        );
+        builder.when(local.op_is_sub).assert_eq(local.rd_val, local.diff);
+        builder.when(local.op_is_sltu).assert_eq(local.rd_val, local.diff_borrow);
+
+        // SLT: `sltu XOR sign(rs1) XOR sign(rs2)`, via the same
+        // `XOR(a,b) = a+b-2ab` identity the bitwise ops above use.
+        let sign_rs1 = local.rs1_bits[ALU_OPERAND_BITS - 1].into();
+        let sign_rs2 = local.rs2_bits[ALU_OPERAND_BITS - 1].into();
+        let xor1 = local.diff_borrow.into() + sign_rs1.clone()
+            - local.diff_borrow.into() * sign_rs1 * AB::Expr::from_canonical_u32(2);
+        let slt = xor1.clone() + sign_rs2.clone() - xor1 * sign_rs2 * AB::Expr::from_canonical_u32(2);
+        builder.when(local.op_is_slt).assert_eq(local.rd_val, slt);
+
+        // `rd_val`'s 32-bit validity for ADD/SUB, range-checked through the
+        // same two-limb decomposition every other sender of `Bus::RangeCheck16`
+        // uses.
+        let add_or_sub: AB::Expr = local.op_is_add.into() + local.op_is_sub.into();
+        builder
+            .when(add_or_sub.clone())
+            .assert_eq(local.rd_val, local.rd_lo.into() + local.rd_hi.into() * two_pow_16);
+
+        // === Cross-chip ALU LogUp bus (receive side), degree-4 extension
+        // field ===
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let beta: [AB::Expr; 4] = raw_bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let bus_values = vec![
+            local.opcode.into(),
+            local.alu_op.into(),
+            local.rs1_val.into(),
+            local.rs2_val.into(),
+            local.rd_val.into(),
+            local.nonce.into(),
+        ];
+        let f_local = ext_add(&alpha, &fingerprint_n(&beta, &bus_values));
+        let f_inv_local: [AB::Expr; 4] = local.f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        // This chip receives on every real row: unlike the fixed small
+        // tables (`RangeCheckChip`/`ShiftPowChip`), there's no enumeration
+        // order to maintain here, so `phi` is just a running sum -- a
+        // first-row base case plus a per-row transition over `next` -- the
+        // same shape `RegisterColumns::reg_bus_phi` uses.
+        let next_slice = main.row_slice(1);
+        let next_arr: &[AB::Var; ALU_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let next: &AluColumns<AB::Var> = next_arr.borrow();
+
+        let neg_is_real_local = AB::Expr::ZERO - local.is_real.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.phi[i], neg_is_real_local.clone() * f_inv_local[i].clone());
+        }
+
+        let bus_values_next = vec![
+            next.opcode.into(),
+            next.alu_op.into(),
+            next.rs1_val.into(),
+            next.rs2_val.into(),
+            next.rd_val.into(),
+            next.nonce.into(),
+        ];
+        let f_next = ext_add(&alpha, &fingerprint_n(&beta, &bus_values_next));
+        let f_inv_next: [AB::Expr; 4] = next.f_inv.map(Into::into);
+        let check_next = ext_mul(&f_next, &f_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_next[i].clone(), one[i].clone());
+        }
+        let neg_is_real_next = AB::Expr::ZERO - next.is_real.into();
+        for i in 0..4 {
+            let term_next = neg_is_real_next.clone() * f_inv_next[i].clone();
+            builder
+                .when_transition()
+                .assert_eq(next.phi[i].into() - local.phi[i].into(), term_next);
+        }
+
+        // === Cross-chip range-check LogUp bus (send side), degree-4
+        // extension field -- same shape `chips::cpu::air::CpuChip::eval`
+        // uses for its own shift limbs, but gated by `add_or_sub` ===
+        let range_f_lo_local = ext_add(&alpha, &ext_from_base(local.rd_lo.into()));
+        let range_f_hi_local = ext_add(&alpha, &ext_from_base(local.rd_hi.into()));
+        let range_f_inv_lo_local: [AB::Expr; 4] = local.range_bus_f_inv_lo.map(Into::into);
+        let range_f_inv_hi_local: [AB::Expr; 4] = local.range_bus_f_inv_hi.map(Into::into);
+        for (f, f_inv) in [
+            (&range_f_lo_local, &range_f_inv_lo_local),
+            (&range_f_hi_local, &range_f_inv_hi_local),
+        ] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+        let range_term_local: [AB::Expr; 4] = std::array::from_fn(|i| {
+            add_or_sub.clone() * (range_f_inv_lo_local[i].clone() + range_f_inv_hi_local[i].clone())
+        });
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.range_bus_phi[i], range_term_local[i].clone());
+        }
+
+        let add_or_sub_next: AB::Expr = next.op_is_add.into() + next.op_is_sub.into();
+        let range_f_lo_next = ext_add(&alpha, &ext_from_base(next.rd_lo.into()));
+        let range_f_hi_next = ext_add(&alpha, &ext_from_base(next.rd_hi.into()));
+        let range_f_inv_lo_next: [AB::Expr; 4] = next.range_bus_f_inv_lo.map(Into::into);
+        let range_f_inv_hi_next: [AB::Expr; 4] = next.range_bus_f_inv_hi.map(Into::into);
+        for (f, f_inv) in [
+            (&range_f_lo_next, &range_f_inv_lo_next),
+            (&range_f_hi_next, &range_f_inv_hi_next),
+        ] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.when_transition().assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+        let range_term_next: [AB::Expr; 4] = std::array::from_fn(|i| {
+            add_or_sub_next.clone() * (range_f_inv_lo_next[i].clone() + range_f_inv_hi_next[i].clone())
+        });
+        for i in 0..4 {
+            builder.when_transition().assert_eq(
+                next.range_bus_phi[i].into() - local.range_bus_phi[i].into(),
+                range_term_next[i].clone(),
+            );
+        }
+    }
+}
+
+impl AluChip {
+    /// The receive side of `Bus::Alu`: this row's operand tuple, counted
+    /// `is_real` times.
+    pub fn receives<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &AluColumns<AB::Var>,
+    ) -> Interaction<AB::Expr> {
+        builder.receive(
+            Bus::Alu,
+            vec![
+                local.opcode.into(),
+                local.alu_op.into(),
+                local.rs1_val.into(),
+                local.rs2_val.into(),
+                local.rd_val.into(),
+                local.nonce.into(),
+            ],
+            local.is_real.into(),
+        )
+    }
+
+    /// The send side of this chip's own `Bus::RangeCheck16` use: `rd_val`'s
+    /// two limbs, gated the same way `eval`'s range-check block is.
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &AluColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        let multiplicity: AB::Expr = local.op_is_add.into() + local.op_is_sub.into();
+        vec![
+            builder.send(Bus::RangeCheck16, vec![local.rd_lo.into()], multiplicity.clone()),
+            builder.send(Bus::RangeCheck16, vec![local.rd_hi.into()], multiplicity),
+        ]
+    }
+
+    /// Generate the ALU trace, row-aligned 1:1 with
+    /// `chips::cpu::trace::generate_cpu_trace`'s trace (same `trace_len`,
+    /// same `nonce = row index`), along with a tally of this chip's
+    /// `Bus::RangeCheck16` sends (`rd_val`'s limbs on ADD/SUB rows) indexed
+    /// by 16-bit value, for `machine::ZkIrMachine` to merge with every other
+    /// sender's before calling `chips::range::RangeCheckChip::generate_trace`.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `MemoryChip::generate_trace`: the bus columns below go through
+    /// `crate::EF`.
+    pub fn generate_trace(
+        &self,
+        trace: &ExecutionTrace,
+    ) -> (RowMajorMatrix<crate::F>, [u64; range::RANGE_CHECK_SIZE]) {
+        type F = crate::F;
+
+        let num_steps = trace.steps.len();
+        let trace_len = num_steps.next_power_of_two().max(2);
+
+        let mut values = vec![F::ZERO; trace_len * AluColumns::<F>::NUM_COLUMNS];
+        let mut range_multiplicities = [0u64; range::RANGE_CHECK_SIZE];
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let beta = raw_bus_beta.map(F::from_canonical_u32);
+
+        let mut phi = [F::ZERO; 4];
+        let mut range_phi = [F::ZERO; 4];
+
+        for i in 0..trace_len {
+            let row_offset = i * AluColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; ALU_NUM_COLUMNS] =
+                (&mut values[row_offset..row_offset + AluColumns::<F>::NUM_COLUMNS])
+                    .try_into()
+                    .unwrap();
+            let cols: &mut AluColumns<F> = row.borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+
+            if let Some(step) = trace.steps.get(i) {
+                cols.opcode = F::from_canonical_u32(step.opcode as u32);
+                if let Some(alu_op) = decode_alu_op(step.opcode, step.funct) {
+                    cols.is_real = F::ONE;
+                    cols.alu_op = F::from_canonical_u8(alu_op);
+
+                    let rs1_raw = step.registers[step.rs1 as usize];
+                    let rs2_raw = step.registers[step.rs2 as usize];
+                    let rd_raw = step.registers[step.rd as usize];
+                    cols.rs1_val = F::from_canonical_u32(rs1_raw);
+                    cols.rs2_val = F::from_canonical_u32(rs2_raw);
+                    cols.rd_val = F::from_canonical_u32(rd_raw);
+
+                    for b in 0..ALU_OPERAND_BITS {
+                        cols.rs1_bits[b] = F::from_canonical_u32((rs1_raw >> b) & 1);
+                        cols.rs2_bits[b] = F::from_canonical_u32((rs2_raw >> b) & 1);
+                    }
+
+                    let sum_raw = rs1_raw as u64 + rs2_raw as u64;
+                    cols.sum = F::from_wrapped_u64(sum_raw);
+                    cols.sum_overflow = F::from_canonical_u64(sum_raw >> 32);
+
+                    let borrow = rs1_raw < rs2_raw;
+                    let diff_raw: u64 = if borrow {
+                        (rs1_raw as u64) + (1u64 << 32) - (rs2_raw as u64)
+                    } else {
+                        (rs1_raw - rs2_raw) as u64
+                    };
+                    cols.diff = F::from_wrapped_u64(diff_raw);
+                    cols.diff_borrow = if borrow { F::ONE } else { F::ZERO };
+
+                    match alu_op {
+                        op::ADD | op::SUB => {
+                            let (lo, hi) = decompose_u32(rd_raw);
+                            cols.rd_lo = F::from_canonical_u32(lo);
+                            cols.rd_hi = F::from_canonical_u32(hi);
+                            range_multiplicities[lo as usize] += 1;
+                            range_multiplicities[hi as usize] += 1;
+                        }
+                        _ => {}
+                    }
+
+                    match alu_op {
+                        op::ADD => cols.op_is_add = F::ONE,
+                        op::SUB => cols.op_is_sub = F::ONE,
+                        op::AND => cols.op_is_and = F::ONE,
+                        op::OR => cols.op_is_or = F::ONE,
+                        op::XOR => cols.op_is_xor = F::ONE,
+                        op::SLT => cols.op_is_slt = F::ONE,
+                        op::SLTU => cols.op_is_sltu = F::ONE,
+                        _ => unreachable!("decode_alu_op only returns the seven codes above"),
+                    }
+                }
+            }
+
+            let bus_values = [cols.opcode, cols.alu_op, cols.rs1_val, cols.rs2_val, cols.rd_val, cols.nonce];
+            let f = ext_add(&alpha, &fingerprint_n(&beta, &bus_values));
+            let f_inv = ext_inverse(f);
+            let neg_is_real = F::ZERO - cols.is_real;
+            for j in 0..4 {
+                phi[j] = phi[j] + neg_is_real * f_inv[j];
+            }
+            cols.f_inv = f_inv;
+            cols.phi = phi;
+
+            let range_multiplicity = cols.op_is_add + cols.op_is_sub;
+            let f_lo = ext_add(&alpha, &ext_from_base(cols.rd_lo));
+            let f_hi = ext_add(&alpha, &ext_from_base(cols.rd_hi));
+            let f_inv_lo = ext_inverse(f_lo);
+            let f_inv_hi = ext_inverse(f_hi);
+            for j in 0..4 {
+                range_phi[j] = range_phi[j] + range_multiplicity * (f_inv_lo[j] + f_inv_hi[j]);
+            }
+            cols.range_bus_f_inv_lo = f_inv_lo;
+            cols.range_bus_f_inv_hi = f_inv_hi;
+            cols.range_bus_phi = range_phi;
+        }
+
+        (RowMajorMatrix::new(values, AluColumns::<F>::NUM_COLUMNS), range_multiplicities)
+    }
+}
+
+/// Shift funct3 values, matching `chips::cpu::trace::decode_shift_funct`'s
+/// packing -- needed here only to rule shifts out (they go to
+/// `ShiftPowChip`, never `Bus::Alu`), not to decode them.
+const SHIFT_FUNCT3_SLL: u8 = 0b001;
+const SHIFT_FUNCT3_SR: u8 = 0b101;
+/// Bit of `funct` this chip reuses as the single varying funct7 bit,
+/// matching `chips::cpu::trace::SHIFT_FUNCT_ARITH_BIT`'s packing.
+const FUNCT_VARIANT_BIT: u8 = 0b1000;
+
+const ALU_FUNCT3_ADD_SUB: u8 = 0b000;
+const ALU_FUNCT3_SLT: u8 = 0b010;
+const ALU_FUNCT3_SLTU: u8 = 0b011;
+const ALU_FUNCT3_XOR: u8 = 0b100;
+const ALU_FUNCT3_OR: u8 = 0b110;
+const ALU_FUNCT3_AND: u8 = 0b111;
+
+/// Independently decide whether `(opcode, funct)` is a real (non-shift)
+/// `OP_ALU`/`OP_ALU_IMM` row and, if so, which of the seven `op` codes it is
+/// -- this chip's own re-derivation of
+/// `chips::cpu::trace::decode_alu_funct`, not a call to it (see the module
+/// doc comment on why this is duplicated rather than shared).
+fn decode_alu_op(opcode: u8, funct: u8) -> Option<u8> {
+    const OP_ALU: u8 = 0b0110011;
+    const OP_ALU_IMM: u8 = 0b0010011;
+    let is_imm = match opcode {
+        OP_ALU => false,
+        OP_ALU_IMM => true,
+        _ => return None,
+    };
+    let funct3 = funct & 0b111;
+    if matches!(funct3, SHIFT_FUNCT3_SLL | SHIFT_FUNCT3_SR) {
+        return None;
+    }
+    Some(match funct3 {
+        ALU_FUNCT3_ADD_SUB => {
+            if !is_imm && funct & FUNCT_VARIANT_BIT != 0 {
+                op::SUB
+            } else {
+                op::ADD
+            }
+        }
+        ALU_FUNCT3_SLT => op::SLT,
+        ALU_FUNCT3_SLTU => op::SLTU,
+        ALU_FUNCT3_XOR => op::XOR,
+        ALU_FUNCT3_OR => op::OR,
+        ALU_FUNCT3_AND => op::AND,
+        _ => unreachable!("funct & 0b111 only has 8 values, all matched above"),
+    })
+}
@@ -9,10 +9,25 @@ use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::Matrix;
 
 use super::columns::{CpuColumns, CPU_NUM_COLUMNS};
+use crate::chips::ext::{ext_add, ext_from_base, ext_mul, ext_one, fingerprint, fingerprint_n};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::chips::range::RANGE_CHECK_BITS;
 
 /// CPU Chip for ZK IR execution
 pub struct CpuChip;
 
+impl Default for CpuChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CpuChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
 impl<F: Field> BaseAir<F> for CpuChip {
     fn width(&self) -> usize {
         CpuColumns::<F>::NUM_COLUMNS
@@ -43,7 +58,9 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
             + local.is_zk_custom.into()
             + local.is_zk_io.into()
             + local.is_halt.into()
-            + local.is_nop.into();
+            + local.is_nop.into()
+            + local.is_float.into()
+            + local.is_shift.into();
 
         builder.assert_one(flag_sum);
 
@@ -63,6 +80,27 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
         self.assert_bool(builder, local.is_halted);
         self.assert_bool(builder, local.branch_taken);
         self.assert_bool(builder, local.mem_is_write);
+        self.assert_bool(builder, local.is_float);
+        self.assert_bool(builder, local.is_shift);
+        self.assert_bool(builder, local.is_right_shift);
+        self.assert_bool(builder, local.is_arith_shift);
+        self.assert_bool(builder, local.sign_bit);
+
+        // FP sub-selectors: each boolean, and at most one set (which one is
+        // set is decoded from `funct` by the `OP_FP` dispatch in `trace.rs`
+        // and not constrained here, so this only rules out more than one
+        // being claimed).
+        self.assert_bool(builder, local.is_fp_add);
+        self.assert_bool(builder, local.is_fp_mul);
+        self.assert_bool(builder, local.is_fp_fma);
+        self.assert_bool(builder, local.is_fp_cmp);
+        self.assert_bool(builder, local.is_fp_convert);
+        let fp_subop_sum: AB::Expr = local.is_fp_add.into()
+            + local.is_fp_mul.into()
+            + local.is_fp_fma.into()
+            + local.is_fp_cmp.into()
+            + local.is_fp_convert.into();
+        builder.assert_zero(fp_subop_sum.clone() * (AB::Expr::ONE - fp_subop_sum));
 
         // ALU operations write result to rd
         builder
@@ -74,6 +112,83 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
             .when(local.is_alu_imm)
             .assert_eq(local.rd_val, local.alu_result);
 
+        // Floating-point operations write their (FPU-chip-constrained) result to rd
+        builder
+            .when(local.is_float)
+            .assert_eq(local.rd_val, local.alu_result);
+
+        // Shift operations write their result to rd
+        builder
+            .when(local.is_shift)
+            .assert_eq(local.rd_val, local.alu_result);
+
+        // === Shift constraints: SLL/SRL/SRA via a power-of-two lookup ===
+        //
+        // `shift_pow` is only constrained to be `2^shift_amount` by the
+        // `Bus::ShiftPow` send in `sends` below (`chips::shift::ShiftPowChip`
+        // owns the actual lookup table); everything here just builds SLL,
+        // SRL, and SRA out of that value.
+        //
+        // SLL (`is_right_shift = 0`): `alu_result` is the truncated 32-bit
+        // left shift, not the full field product -- `shift_overflow` is the
+        // witnessed high limb `rs1_val * shift_pow` sheds when wrapped to 32
+        // bits, so `alu_result = rs1_val * shift_pow - shift_overflow * 2^32`
+        // pins `alu_result` down to the one 32-bit value `shift_overflow`
+        // range-checks it against (below), instead of the bare field
+        // product, which for large `rs1_val`/`shift_pow` is a different,
+        // out-of-range number.
+        let not_right_shift: AB::Expr = AB::Expr::ONE - local.is_right_shift.into();
+        let two_pow_32 = AB::Expr::from_wrapped_u64(1u64 << 32);
+        builder
+            .when(local.is_shift)
+            .when(not_right_shift.clone())
+            .assert_eq(
+                local.rs1_val.into() * local.shift_pow.into(),
+                local.alu_result.into() + local.shift_overflow.into() * two_pow_32.clone(),
+            );
+
+        // `shift_overflow`'s 32-bit validity, range-checked via the same
+        // two-limb decomposition as `shift_remainder` below (see `sends`
+        // below).
+        let two_pow_16 = AB::Expr::from_canonical_u32(1 << RANGE_CHECK_BITS);
+        builder.when(local.is_shift).when(not_right_shift).assert_eq(
+            local.shift_overflow,
+            local.shift_overflow_lo.into() + local.shift_overflow_hi.into() * two_pow_16.clone(),
+        );
+
+        // SRL/SRA (`is_right_shift = 1`): division with a witnessed
+        // remainder, `rs1_val = quotient * shift_pow + shift_remainder`,
+        // where `quotient` is the logical (unsigned) shift result. SRA's
+        // arithmetic result then adds a sign-extension correction on top of
+        // that logical quotient: `sign_bit * 2^32 * (1 - shift_pow_inv)` is
+        // `sign_bit` copies of "the top `shift_amount` bits, all set" (since
+        // `2^32 * (1 - 1/shift_pow) == 2^32 - 2^(32 - shift_amount)`), zero
+        // whenever `sign_bit` is 0 or `shift_amount` is 0.
+        builder
+            .when(local.is_shift)
+            .when(local.is_right_shift)
+            .assert_eq(
+                local.shift_pow.into() * local.shift_pow_inv.into(),
+                AB::Expr::ONE,
+            );
+
+        let sign_correction: AB::Expr =
+            local.is_arith_shift.into() * local.sign_bit.into() * two_pow_32.clone() * (AB::Expr::ONE - local.shift_pow_inv.into());
+        let quotient: AB::Expr = local.alu_result.into() - sign_correction;
+        builder.when(local.is_shift).when(local.is_right_shift).assert_eq(
+            local.rs1_val,
+            quotient * local.shift_pow.into() + local.shift_remainder.into(),
+        );
+
+        // `shift_remainder`'s 32-bit validity is range-checked through the
+        // same two-limb decomposition `chips::memory::MemoryChip` uses (see
+        // `sends` below); bounding it below `shift_pow` specifically is the
+        // documented TODO on `CpuColumns::shift_remainder`.
+        builder.when(local.is_shift).when(local.is_right_shift).assert_eq(
+            local.shift_remainder,
+            local.shift_remainder_lo.into() + local.shift_remainder_hi.into() * two_pow_16,
+        );
+
         // PC transitions
         let pc_plus_4: AB::Expr = local.pc.into() + AB::Expr::from_canonical_u32(4);
         let pc_plus_imm: AB::Expr = local.pc.into() + local.imm.into();
@@ -85,7 +200,9 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
             + local.is_store.into()
             + local.is_lui_auipc.into()
             + local.is_zk_custom.into()
-            + local.is_zk_io.into();
+            + local.is_zk_io.into()
+            + local.is_float.into()
+            + local.is_shift.into();
 
         builder
             .when(is_sequential.clone())
@@ -134,7 +251,9 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
             .when(AB::Expr::ONE - local.is_nop.into())
             .assert_eq(next.cycle, local.cycle.into() + AB::Expr::ONE);
 
-        // Register r0 is always zero (enforced via register file lookup)
+        // Register r0 is always zero: enforced by `chips::register::RegisterChip`'s
+        // hard constraint on its sorted trace, tied back to this chip's `rs1_val`/
+        // `rs2_val`/`rd_val` via the register bus below.
 
         // Memory constraints (linked via permutation with memory chip)
         // Load: mem_is_write = 0, mem_addr = rs1_val + imm, rd_val = mem_val
@@ -142,6 +261,533 @@ impl<AB: AirBuilder> Air<AB> for CpuChip {
 
         // Store: mem_is_write = 1, mem_addr = rs1_val + imm, mem_val = rs2_val
         builder.when(local.is_store).assert_one(local.mem_is_write);
+
+        // === Cross-chip memory LogUp bus, degree-4 extension field ===
+        //
+        // `mem_bus_phi` accumulates `multiplicity / fingerprint` (an
+        // extension-field element, stored as 4 base-field columns) over
+        // every row of this chip; `machine::ZkIrMachine` checks it sums to
+        // zero against the memory chip's own accumulator over its `exec_*`
+        // columns (see `MemoryColumns::mem_bus_phi`), which is what
+        // actually ties the two chips' independent traces together.
+        let (bus_alpha, bus_beta) = bus_challenges();
+        let alpha: [AB::Expr; 4] = bus_alpha.map(AB::Expr::from_canonical_u32);
+        let beta: [AB::Expr; 4] = bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let f_local = fingerprint(
+            &beta,
+            local.mem_addr.into(),
+            local.cycle.into(),
+            local.mem_val.into(),
+            local.mem_is_write.into(),
+        );
+        let f_local = ext_add(&alpha, &f_local);
+
+        // `mem_bus_f_inv` is witnessed as the fingerprint's extension-field
+        // inverse (never zero with overwhelming probability once alpha/beta
+        // are real Fiat-Shamir challenges), which lets the running sum
+        // below avoid an in-circuit division.
+        let f_inv_local: [AB::Expr; 4] = local.mem_bus_f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let mem_multiplicity: AB::Expr = local.is_load.into() + local.is_store.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.mem_bus_phi[i], f_inv_local[i].clone() * mem_multiplicity.clone());
+        }
+
+        let next_mem_multiplicity: AB::Expr = next.is_load.into() + next.is_store.into();
+        let f_inv_next: [AB::Expr; 4] = next.mem_bus_f_inv.map(Into::into);
+        for i in 0..4 {
+            let term_next = f_inv_next[i].clone() * next_mem_multiplicity.clone();
+            builder.when_transition().assert_eq(
+                next.mem_bus_phi[i].into() - local.mem_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip ALU LogUp bus, degree-4 extension field ===
+        //
+        // Same closure technique as the memory bus above, fingerprinting
+        // `(opcode, alu_op, rs1_val, rs2_val, rd_val, nonce)` via
+        // `fingerprint_n` and gated by `alu_multiplicity` (the `is_alu +
+        // is_alu_imm` selector `sends` below also uses): this is what
+        // actually binds `rd_val` to `alu_op(rs1_val, rs2_val)` for ALU rows,
+        // since this chip's own constraints just copy `alu_result` into
+        // `rd_val` without recomputing it.
+        let alu_multiplicity_local: AB::Expr = local.is_alu.into() + local.is_alu_imm.into();
+        let alu_values_local = vec![
+            local.opcode.into(),
+            local.alu_op.into(),
+            local.rs1_val.into(),
+            local.rs2_val.into(),
+            local.rd_val.into(),
+            local.nonce.into(),
+        ];
+        let f_alu_local = ext_add(&alpha, &fingerprint_n(&beta, &alu_values_local));
+        let f_alu_inv_local: [AB::Expr; 4] = local.alu_bus_f_inv.map(Into::into);
+        let check_alu_local = ext_mul(&f_alu_local, &f_alu_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_alu_local[i].clone(), one[i].clone());
+        }
+
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.alu_bus_phi[i],
+                alu_multiplicity_local.clone() * f_alu_inv_local[i].clone(),
+            );
+        }
+
+        let alu_multiplicity_next: AB::Expr = next.is_alu.into() + next.is_alu_imm.into();
+        let alu_values_next = vec![
+            next.opcode.into(),
+            next.alu_op.into(),
+            next.rs1_val.into(),
+            next.rs2_val.into(),
+            next.rd_val.into(),
+            next.nonce.into(),
+        ];
+        let f_alu_next = ext_add(&alpha, &fingerprint_n(&beta, &alu_values_next));
+        let f_alu_inv_next: [AB::Expr; 4] = next.alu_bus_f_inv.map(Into::into);
+        let check_alu_next = ext_mul(&f_alu_next, &f_alu_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_alu_next[i].clone(), one[i].clone());
+        }
+        for i in 0..4 {
+            let term_next = alu_multiplicity_next.clone() * f_alu_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.alu_bus_phi[i].into() - local.alu_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip FPU LogUp bus, degree-4 extension field ===
+        //
+        // Same closure technique as the ALU bus above, fingerprinting
+        // `(funct, rs1_val, rs2_val, rd_val, nonce)` via `fingerprint_n` and
+        // gated by `is_float` (the selector `sends` below also uses): this
+        // is what actually binds `rd_val` to `chips::fpu::FpuChip`'s
+        // recomputed result for RV32F rows.
+        let fpu_values_local = vec![
+            local.funct.into(),
+            local.rs1_val.into(),
+            local.rs2_val.into(),
+            local.rd_val.into(),
+            local.nonce.into(),
+        ];
+        let f_fpu_local = ext_add(&alpha, &fingerprint_n(&beta, &fpu_values_local));
+        let f_fpu_inv_local: [AB::Expr; 4] = local.fpu_bus_f_inv.map(Into::into);
+        let check_fpu_local = ext_mul(&f_fpu_local, &f_fpu_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_fpu_local[i].clone(), one[i].clone());
+        }
+
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.fpu_bus_phi[i],
+                local.is_float.into() * f_fpu_inv_local[i].clone(),
+            );
+        }
+
+        let fpu_values_next = vec![
+            next.funct.into(),
+            next.rs1_val.into(),
+            next.rs2_val.into(),
+            next.rd_val.into(),
+            next.nonce.into(),
+        ];
+        let f_fpu_next = ext_add(&alpha, &fingerprint_n(&beta, &fpu_values_next));
+        let f_fpu_inv_next: [AB::Expr; 4] = next.fpu_bus_f_inv.map(Into::into);
+        let check_fpu_next = ext_mul(&f_fpu_next, &f_fpu_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_fpu_next[i].clone(), one[i].clone());
+        }
+        for i in 0..4 {
+            let term_next = next.is_float.into() * f_fpu_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.fpu_bus_phi[i].into() - local.fpu_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip register LogUp bus, degree-4 extension field ===
+        //
+        // Same closure technique as the memory bus above, but every
+        // non-padding row contributes three fingerprint terms instead of
+        // one: an `rs1` read, an `rs2` read, and an `rd` write, all sharing
+        // this row's `cycle`. `chips::register::RegisterChip` sorts by
+        // `(reg_index, cycle)`, so the three touches need a strict order
+        // within a cycle; `seq = cycle*3 + slot` (0/1/2 for rs1/rs2/rd)
+        // gives them one, matching `RegisterAccess::cycle`'s doc comment.
+        // Padding (NOP) rows contribute nothing, the same way padding rows
+        // contribute no memory-bus term.
+        let reg_multiplicity: AB::Expr = AB::Expr::ONE - local.is_nop.into();
+        let cycle3_local: AB::Expr = local.cycle.into() * AB::Expr::from_canonical_u32(3);
+
+        let f_rs1_local = ext_add(
+            &alpha,
+            &fingerprint(&beta, local.rs1.into(), cycle3_local.clone(), local.rs1_val.into(), AB::Expr::ZERO),
+        );
+        let f_rs1_inv_local: [AB::Expr; 4] = local.reg_rs1_bus_f_inv.map(Into::into);
+        let check_rs1_local = ext_mul(&f_rs1_local, &f_rs1_inv_local);
+
+        let f_rs2_local = ext_add(
+            &alpha,
+            &fingerprint(
+                &beta,
+                local.rs2.into(),
+                cycle3_local.clone() + AB::Expr::ONE,
+                local.rs2_val.into(),
+                AB::Expr::ZERO,
+            ),
+        );
+        let f_rs2_inv_local: [AB::Expr; 4] = local.reg_rs2_bus_f_inv.map(Into::into);
+        let check_rs2_local = ext_mul(&f_rs2_local, &f_rs2_inv_local);
+
+        let f_rd_local = ext_add(
+            &alpha,
+            &fingerprint(
+                &beta,
+                local.rd.into(),
+                cycle3_local + AB::Expr::from_canonical_u32(2),
+                local.rd_val.into(),
+                AB::Expr::ONE,
+            ),
+        );
+        let f_rd_inv_local: [AB::Expr; 4] = local.reg_rd_bus_f_inv.map(Into::into);
+        let check_rd_local = ext_mul(&f_rd_local, &f_rd_inv_local);
+
+        for i in 0..4 {
+            builder.assert_eq(check_rs1_local[i].clone(), one[i].clone());
+            builder.assert_eq(check_rs2_local[i].clone(), one[i].clone());
+            builder.assert_eq(check_rd_local[i].clone(), one[i].clone());
+        }
+
+        let reg_phi_term_local = ext_add(&ext_add(&f_rs1_inv_local, &f_rs2_inv_local), &f_rd_inv_local);
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.reg_bus_phi[i],
+                reg_multiplicity.clone() * reg_phi_term_local[i].clone(),
+            );
+        }
+
+        let next_reg_multiplicity: AB::Expr = AB::Expr::ONE - next.is_nop.into();
+        let f_rs1_inv_next: [AB::Expr; 4] = next.reg_rs1_bus_f_inv.map(Into::into);
+        let f_rs2_inv_next: [AB::Expr; 4] = next.reg_rs2_bus_f_inv.map(Into::into);
+        let f_rd_inv_next: [AB::Expr; 4] = next.reg_rd_bus_f_inv.map(Into::into);
+        let reg_phi_term_next = ext_add(&ext_add(&f_rs1_inv_next, &f_rs2_inv_next), &f_rd_inv_next);
+        for i in 0..4 {
+            let term_next = next_reg_multiplicity.clone() * reg_phi_term_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.reg_bus_phi[i].into() - local.reg_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip program LogUp bus, degree-4 extension field ===
+        //
+        // Same closure technique as the memory/register buses above, but
+        // fingerprinting the full 7-value fetch-decode tuple via
+        // `fingerprint_n` instead of `fingerprint`'s fixed 4 terms. Gated by
+        // `reg_multiplicity` (padding rows send nothing), the same selector
+        // the register bus above uses, for the same reason: a NOP padding
+        // row has no real program entry to match.
+        let program_multiplicity = reg_multiplicity.clone();
+        let program_values_local = vec![
+            local.pc.into(),
+            local.opcode.into(),
+            local.rs1.into(),
+            local.rs2.into(),
+            local.rd.into(),
+            local.imm.into(),
+            local.funct.into(),
+        ];
+        let f_program_local = ext_add(&alpha, &fingerprint_n(&beta, &program_values_local));
+        let f_program_inv_local: [AB::Expr; 4] = local.program_bus_f_inv.map(Into::into);
+        let check_program_local = ext_mul(&f_program_local, &f_program_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_program_local[i].clone(), one[i].clone());
+        }
+
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.program_bus_phi[i],
+                program_multiplicity.clone() * f_program_inv_local[i].clone(),
+            );
+        }
+
+        let next_program_multiplicity = next_reg_multiplicity.clone();
+        let program_values_next = vec![
+            next.pc.into(),
+            next.opcode.into(),
+            next.rs1.into(),
+            next.rs2.into(),
+            next.rd.into(),
+            next.imm.into(),
+            next.funct.into(),
+        ];
+        let f_program_next = ext_add(&alpha, &fingerprint_n(&beta, &program_values_next));
+        let f_program_inv_next: [AB::Expr; 4] = next.program_bus_f_inv.map(Into::into);
+        let check_program_next = ext_mul(&f_program_next, &f_program_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_program_next[i].clone(), one[i].clone());
+        }
+        for i in 0..4 {
+            let term_next = next_program_multiplicity.clone() * f_program_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.program_bus_phi[i].into() - local.program_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip shift-power LogUp bus, degree-4 extension field ===
+        //
+        // Same closure technique as the buses above, fingerprinting
+        // `(shift_amount, shift_pow)` and gating by `is_shift` (non-shift
+        // rows contribute nothing, the same as padding rows on the program
+        // bus above).
+        let shift_values_local = vec![local.shift_amount.into(), local.shift_pow.into()];
+        let f_shift_local = ext_add(&alpha, &fingerprint_n(&beta, &shift_values_local));
+        let f_shift_inv_local: [AB::Expr; 4] = local.shift_bus_f_inv.map(Into::into);
+        let check_shift_local = ext_mul(&f_shift_local, &f_shift_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_shift_local[i].clone(), one[i].clone());
+        }
+
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.shift_bus_phi[i],
+                local.is_shift.into() * f_shift_inv_local[i].clone(),
+            );
+        }
+
+        let shift_values_next = vec![next.shift_amount.into(), next.shift_pow.into()];
+        let f_shift_next = ext_add(&alpha, &fingerprint_n(&beta, &shift_values_next));
+        let f_shift_inv_next: [AB::Expr; 4] = next.shift_bus_f_inv.map(Into::into);
+        let check_shift_next = ext_mul(&f_shift_next, &f_shift_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_shift_next[i].clone(), one[i].clone());
+        }
+        for i in 0..4 {
+            let term_next = next.is_shift.into() * f_shift_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.shift_bus_phi[i].into() - local.shift_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip range-check LogUp bus (send side), degree-4
+        // extension field ===
+        //
+        // Same fingerprint/phi shape `chips::memory::MemoryChip::eval` uses
+        // for its own `Bus::RangeCheck16` sends (`alpha + value`, no `beta`
+        // needed), but this row only ever sends one limb pair: the
+        // remainder pair when `is_right_shift`, the overflow pair
+        // otherwise, selected the same way `sends` below already gates
+        // them. `machine::check_range_bus_closure` sums this against
+        // `MemoryColumns::range_bus_phi`, `AluColumns::range_bus_phi`, and
+        // `RangeCheckColumns::phi`.
+        let lo_val_local: AB::Expr = local.is_right_shift.into() * local.shift_remainder_lo.into()
+            + (AB::Expr::ONE - local.is_right_shift.into()) * local.shift_overflow_lo.into();
+        let hi_val_local: AB::Expr = local.is_right_shift.into() * local.shift_remainder_hi.into()
+            + (AB::Expr::ONE - local.is_right_shift.into()) * local.shift_overflow_hi.into();
+
+        let range_f_lo_local = ext_add(&alpha, &ext_from_base(lo_val_local));
+        let range_f_hi_local = ext_add(&alpha, &ext_from_base(hi_val_local));
+        let range_f_inv_lo_local: [AB::Expr; 4] = local.range_bus_f_inv_lo.map(Into::into);
+        let range_f_inv_hi_local: [AB::Expr; 4] = local.range_bus_f_inv_hi.map(Into::into);
+        for (f, f_inv) in [(&range_f_lo_local, &range_f_inv_lo_local), (&range_f_hi_local, &range_f_inv_hi_local)] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+
+        let range_term_local: [AB::Expr; 4] = std::array::from_fn(|i| {
+            local.is_shift.into() * (range_f_inv_lo_local[i].clone() + range_f_inv_hi_local[i].clone())
+        });
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.range_bus_phi[i], range_term_local[i].clone());
+        }
+
+        let lo_val_next: AB::Expr = next.is_right_shift.into() * next.shift_remainder_lo.into()
+            + (AB::Expr::ONE - next.is_right_shift.into()) * next.shift_overflow_lo.into();
+        let hi_val_next: AB::Expr = next.is_right_shift.into() * next.shift_remainder_hi.into()
+            + (AB::Expr::ONE - next.is_right_shift.into()) * next.shift_overflow_hi.into();
+        let range_f_lo_next = ext_add(&alpha, &ext_from_base(lo_val_next));
+        let range_f_hi_next = ext_add(&alpha, &ext_from_base(hi_val_next));
+        let range_f_inv_lo_next: [AB::Expr; 4] = next.range_bus_f_inv_lo.map(Into::into);
+        let range_f_inv_hi_next: [AB::Expr; 4] = next.range_bus_f_inv_hi.map(Into::into);
+        for (f, f_inv) in [(&range_f_lo_next, &range_f_inv_lo_next), (&range_f_hi_next, &range_f_inv_hi_next)] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.when_transition().assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+        let range_term_next: [AB::Expr; 4] = std::array::from_fn(|i| {
+            next.is_shift.into() * (range_f_inv_lo_next[i].clone() + range_f_inv_hi_next[i].clone())
+        });
+        for i in 0..4 {
+            builder.when_transition().assert_eq(
+                next.range_bus_phi[i].into() - local.range_bus_phi[i].into(),
+                range_term_next[i].clone(),
+            );
+        }
+    }
+}
+
+impl CpuChip {
+    /// The interaction bus tuples this row sends. The ALU chip receives the
+    /// `(opcode, alu_op, rs1_val, rs2_val, rd_val, nonce)` tuple whenever this
+    /// row is an ALU or ALU-immediate op (the real closure for this one runs
+    /// through the hand-rolled `alu_bus_phi` column in `eval` above, like the
+    /// memory bus, not this generic send); the memory chip receives the
+    /// access tuple whenever this row loads or stores; the FPU chip receives
+    /// the operand tuple whenever this row is an RV32F op (IEEE-754
+    /// arithmetic can't be expressed as a single field constraint, so it's
+    /// always routed off-chip); the shift-power chip receives
+    /// `(shift_amount, shift_pow)` whenever this row is a shift, and the
+    /// range-check chip receives `shift_remainder`'s two limbs (SRL/SRA rows)
+    /// or `shift_overflow`'s two limbs (SLL rows) under the matching gate.
+    /// On every non-padding row, also sends its decoded fetch-decode tuple to
+    /// the program chip (see `chips::program::ProgramChip`) and its `rs1`
+    /// read, `rs2` read, and `rd` write to the register chip (see
+    /// `chips::register::RegisterChip`; the real closure for both of those
+    /// runs through the hand-rolled `program_bus_phi`/`reg_bus_phi` columns in
+    /// `eval` above, like the memory and ALU buses, not this generic send --
+    /// this list is otherwise decorative, since nothing aggregates its return
+    /// value). The `nonce` column keeps otherwise-identical tuples distinct
+    /// so the LogUp running sum can't cancel two unrelated rows against each
+    /// other.
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &CpuColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        let alu_multiplicity: AB::Expr = local.is_alu.into() + local.is_alu_imm.into();
+        let mem_multiplicity: AB::Expr = local.is_load.into() + local.is_store.into();
+        let fpu_multiplicity: AB::Expr = local.is_float.into();
+        let shift_multiplicity: AB::Expr = local.is_shift.into();
+        let shift_remainder_multiplicity: AB::Expr = local.is_shift.into() * local.is_right_shift.into();
+        let shift_overflow_multiplicity: AB::Expr =
+            local.is_shift.into() * (AB::Expr::ONE - local.is_right_shift.into());
+        let reg_multiplicity: AB::Expr = AB::Expr::ONE - local.is_nop.into();
+        let cycle3: AB::Expr = local.cycle.into() * AB::Expr::from_canonical_u32(3);
+
+        vec![
+            builder.send(
+                Bus::Alu,
+                vec![
+                    local.opcode.into(),
+                    local.alu_op.into(),
+                    local.rs1_val.into(),
+                    local.rs2_val.into(),
+                    local.rd_val.into(),
+                    local.nonce.into(),
+                ],
+                alu_multiplicity,
+            ),
+            builder.send(
+                Bus::Memory,
+                vec![
+                    local.mem_addr.into(),
+                    local.mem_val.into(),
+                    local.mem_is_write.into(),
+                    local.cycle.into(),
+                    local.nonce.into(),
+                ],
+                mem_multiplicity,
+            ),
+            builder.send(
+                Bus::Fpu,
+                vec![
+                    local.funct.into(),
+                    local.rs1_val.into(),
+                    local.rs2_val.into(),
+                    local.rd_val.into(),
+                    local.nonce.into(),
+                ],
+                fpu_multiplicity,
+            ),
+            builder.send(
+                Bus::ShiftPow,
+                vec![local.shift_amount.into(), local.shift_pow.into()],
+                shift_multiplicity,
+            ),
+            builder.send(
+                Bus::RangeCheck16,
+                vec![local.shift_remainder_lo.into()],
+                shift_remainder_multiplicity.clone(),
+            ),
+            builder.send(
+                Bus::RangeCheck16,
+                vec![local.shift_remainder_hi.into()],
+                shift_remainder_multiplicity,
+            ),
+            builder.send(
+                Bus::RangeCheck16,
+                vec![local.shift_overflow_lo.into()],
+                shift_overflow_multiplicity.clone(),
+            ),
+            builder.send(
+                Bus::RangeCheck16,
+                vec![local.shift_overflow_hi.into()],
+                shift_overflow_multiplicity,
+            ),
+            builder.send(
+                Bus::Program,
+                vec![
+                    local.pc.into(),
+                    local.opcode.into(),
+                    local.rs1.into(),
+                    local.rs2.into(),
+                    local.rd.into(),
+                    local.imm.into(),
+                    local.funct.into(),
+                ],
+                reg_multiplicity.clone(),
+            ),
+            builder.send(
+                Bus::Register,
+                vec![
+                    local.rs1.into(),
+                    local.rs1_val.into(),
+                    AB::Expr::ZERO,
+                    cycle3.clone(),
+                    local.nonce.into(),
+                ],
+                reg_multiplicity.clone(),
+            ),
+            builder.send(
+                Bus::Register,
+                vec![
+                    local.rs2.into(),
+                    local.rs2_val.into(),
+                    AB::Expr::ZERO,
+                    cycle3.clone() + AB::Expr::ONE,
+                    local.nonce.into(),
+                ],
+                reg_multiplicity.clone(),
+            ),
+            builder.send(
+                Bus::Register,
+                vec![
+                    local.rd.into(),
+                    local.rd_val.into(),
+                    AB::Expr::ONE,
+                    cycle3 + AB::Expr::from_canonical_u32(2),
+                    local.nonce.into(),
+                ],
+                reg_multiplicity,
+            ),
+        ]
     }
 }
 
@@ -152,11 +798,20 @@ impl CpuChip {
         builder.assert_zero(val.into() * (AB::Expr::ONE - val.into()));
     }
 
-    /// Generate the trace matrix for this chip
-    pub fn generate_trace<F: Field>(
+    /// Generate the trace matrix for this chip. Fixed to `crate::F` (see
+    /// `trace::generate_cpu_trace`'s doc comment).
+    /// Generate this chip's trace, along with the program/range-check/
+    /// shift-power multiplicity tallies other chips' `generate_trace` need
+    /// -- see `trace::generate_cpu_trace`.
+    pub fn generate_trace(
         &self,
         trace: &crate::ExecutionTrace,
-    ) -> RowMajorMatrix<F> {
+    ) -> (
+        RowMajorMatrix<crate::F>,
+        Vec<u64>,
+        [u64; crate::chips::range::RANGE_CHECK_SIZE],
+        [u64; crate::chips::shift::SHIFT_TABLE_SIZE],
+    ) {
         super::trace::generate_cpu_trace(trace)
     }
 }
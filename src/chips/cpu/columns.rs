@@ -4,7 +4,7 @@ use std::borrow::{Borrow, BorrowMut};
 
 /// CPU trace columns
 ///
-/// Total: 32 columns organized into logical groups
+/// Total: 120 columns organized into logical groups
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
 pub struct CpuColumns<T> {
@@ -40,7 +40,7 @@ pub struct CpuColumns<T> {
     /// Value to write to rd register
     pub rd_val: T,
 
-    // === Opcode flags (12 columns, one-hot) ===
+    // === Opcode flags (14 columns, one-hot) ===
     /// ALU operation (ADD, SUB, MUL, etc.)
     pub is_alu: T,
     /// ALU immediate operation (ADDI, etc.)
@@ -65,11 +65,81 @@ pub struct CpuColumns<T> {
     pub is_halt: T,
     /// NOP (padding rows)
     pub is_nop: T,
+    /// RV32F floating-point instruction (OP-FP or one of the R4-type FMA majors)
+    pub is_float: T,
+    /// Shift instruction (SLL/SLLI, SRL/SRLI, SRA/SRAI)
+    pub is_shift: T,
+
+    // === Floating-point sub-selectors (5 columns, one-hot when is_float) ===
+    /// FADD.S / FSUB.S
+    pub is_fp_add: T,
+    /// FMUL.S / FDIV.S / FSQRT.S
+    pub is_fp_mul: T,
+    /// FMADD.S / FMSUB.S / FNMSUB.S / FNMADD.S
+    pub is_fp_fma: T,
+    /// FEQ.S / FLT.S / FLE.S / FMIN.S / FMAX.S
+    pub is_fp_cmp: T,
+    /// FCVT.W.S / FCVT.S.W and friends (int <-> float conversion)
+    pub is_fp_convert: T,
+
+    // === Shift sub-selectors and witnesses (12 columns, see `air.rs`'s SLL /
+    // SRL / SRA constraints) ===
+    /// 1 for SRL/SRL.../SRA (right shift), 0 for SLL (left shift) -- selects
+    /// which of the multiply/divide constraint forms below applies.
+    pub is_right_shift: T,
+    /// 1 for SRA/SRAI (arithmetic right shift), meaningless unless
+    /// `is_right_shift` is also set.
+    pub is_arith_shift: T,
+    /// Shift amount, masked to 5 bits at decode time (so always `0..32`).
+    pub shift_amount: T,
+    /// `2^shift_amount`, constrained against `chips::shift::ShiftPowChip`'s
+    /// lookup table rather than computed in-circuit.
+    pub shift_pow: T,
+    /// Witnessed inverse of `shift_pow`, used only to build the SRA
+    /// sign-extension correction below (never zero: `shift_pow` is always a
+    /// power of two).
+    pub shift_pow_inv: T,
+    /// SRL/SRA division remainder: `rs1_val = quotient * shift_pow +
+    /// shift_remainder`.
+    ///
+    /// TODO: only proven to be a valid 32-bit value (via the
+    /// `shift_remainder_lo`/`_hi` range-check below), not that it's below
+    /// `shift_pow` specifically -- that needs a dedicated less-than gadget
+    /// this chip doesn't have yet, the same kind of gap
+    /// `chips::range::RangeCheckChip::generate_trace` documents for its own
+    /// multiplicity.
+    pub shift_remainder: T,
+    /// Low 16 bits of `shift_remainder`, range-checked via `Bus::RangeCheck16`.
+    pub shift_remainder_lo: T,
+    /// High 16 bits of `shift_remainder`, range-checked via `Bus::RangeCheck16`.
+    pub shift_remainder_hi: T,
+    /// SLL's overflow limb: `rs1_val * shift_pow == alu_result +
+    /// shift_overflow * 2^32`, i.e. the bits the true 32-bit left shift
+    /// truncates away. Binds `alu_result` to the truncated 32-bit result
+    /// instead of the untruncated field product.
+    pub shift_overflow: T,
+    /// Low 16 bits of `shift_overflow`, range-checked via `Bus::RangeCheck16`.
+    pub shift_overflow_lo: T,
+    /// High 16 bits of `shift_overflow`, range-checked via `Bus::RangeCheck16`.
+    pub shift_overflow_hi: T,
+    /// Witnessed top bit of `rs1_val`, used for SRA's sign-extension
+    /// correction.
+    ///
+    /// TODO: only asserted boolean, not proven to actually be `rs1_val`'s
+    /// top bit -- that needs its own bit-decomposition range check, which
+    /// this chip doesn't build yet.
+    pub sign_bit: T,
 
     // === ALU operation (4 columns) ===
-    /// ALU operation selector
+    /// ALU operation selector, decoded from `funct` by `trace::decode_alu_funct` for
+    /// `is_alu`/`is_alu_imm` rows -- the value `chips::alu::AluChip` receives
+    /// over `Bus::Alu` and recomputes the operation from, rather than
+    /// trusting `alu_result` directly (see that field below).
     pub alu_op: T,
-    /// ALU result
+    /// ALU or FPU result, written to rd. For `is_alu`/`is_alu_imm` rows, only
+    /// actually proven equal to `op(rs1_val, rs2_val)` by the `Bus::Alu`
+    /// closure (`alu_bus_phi` below / `chips::alu::AluChip`); this chip's own
+    /// constraints just copy it into `rd_val`.
     pub alu_result: T,
     /// Branch condition result (1 if taken)
     pub branch_taken: T,
@@ -83,10 +153,144 @@ pub struct CpuColumns<T> {
     pub mem_val: T,
     /// Memory operation type (1 = write, 0 = read)
     pub mem_is_write: T,
+
+    // === Interaction bus (1 column) ===
+    /// Row-unique nonce mixed into every tuple this row sends on the
+    /// interaction bus, so two identical operations don't cancel out.
+    pub nonce: T,
+
+    // === Cross-chip memory LogUp bus (8 columns: two degree-4 extension
+    // field elements, see `chips::ext`) ===
+    // Closes the gap the old comment in `MemoryChip::eval` only described:
+    // nothing previously tied the CPU's claimed memory accesses to what the
+    // memory chip received. See `chips::interaction` and `machine`.
+    /// Inverse, in the degree-4 extension, of this row's memory-bus
+    /// fingerprint `alpha + mem_addr + beta*cycle + beta^2*mem_val +
+    /// beta^3*mem_is_write`, witnessed so the `mem_bus_phi` update below
+    /// avoids an in-circuit division.
+    pub mem_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the send side of the
+    /// CPU<->Memory LogUp bus. `machine::ZkIrMachine` checks this sums to
+    /// zero against the memory chip's receive side.
+    pub mem_bus_phi: [T; 4],
+
+    // === Cross-chip ALU LogUp bus (8 columns: two degree-4 extension field
+    // elements, see `chips::ext`) ===
+    // Closes the gap `chips::alu::AluChip`'s own doc comment flags: `sends`
+    // below has always offered `(opcode, alu_op, rs1_val, rs2_val, rd_val,
+    // nonce)` onto `Bus::Alu`, but nothing used to receive it, so a prover
+    // could claim any `rd_val` at all for an ALU row.
+    /// Inverse, in the degree-4 extension, of this row's ALU-bus fingerprint
+    /// over `(opcode, alu_op, rs1_val, rs2_val, rd_val, nonce)`.
+    pub alu_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the send side of the
+    /// CPU<->ALU LogUp bus, gated by `is_alu + is_alu_imm`.
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `chips::alu::AluColumns::phi`.
+    pub alu_bus_phi: [T; 4],
+
+    // === Cross-chip FPU LogUp bus (8 columns: two degree-4 extension field
+    // elements, see `chips::ext`) ===
+    // Closes the gap `chips::fpu::FpuChip`'s own doc comment flags: `sends`
+    // below has always offered `(funct, rs1_val, rs2_val, rd_val, nonce)`
+    // onto `Bus::Fpu`, but nothing used to receive it, so a prover could
+    // claim any `rd_val` at all for an RV32F row.
+    /// Inverse, in the degree-4 extension, of this row's FPU-bus fingerprint
+    /// over `(funct, rs1_val, rs2_val, rd_val, nonce)`.
+    pub fpu_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the send side of the
+    /// CPU<->FPU LogUp bus, gated by `is_float`. `machine::ZkIrMachine`
+    /// checks this sums to zero against `chips::fpu::FpuColumns::phi`.
+    pub fpu_bus_phi: [T; 4],
+
+    // === Cross-chip register LogUp bus (16 columns: four degree-4
+    // extension field elements, see `chips::ext`) ===
+    // Every row touches up to three registers at once (an `rs1` read, an
+    // `rs2` read, an `rd` write), unlike the single memory access above, so
+    // there are three witnessed fingerprint inverses feeding one running
+    // sum. See `chips::register::RegisterChip` and `machine`.
+    /// Inverse, in the degree-4 extension, of this row's `rs1`-read
+    /// register-bus fingerprint.
+    pub reg_rs1_bus_f_inv: [T; 4],
+    /// Inverse, in the degree-4 extension, of this row's `rs2`-read
+    /// register-bus fingerprint.
+    pub reg_rs2_bus_f_inv: [T; 4],
+    /// Inverse, in the degree-4 extension, of this row's `rd`-write
+    /// register-bus fingerprint.
+    pub reg_rd_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` summed over this row's three register touches -- the
+    /// send side of the CPU<->Register LogUp bus. `machine::ZkIrMachine`
+    /// checks this sums to zero against the register chip's receive side.
+    pub reg_bus_phi: [T; 4],
+
+    // === Cross-chip program LogUp bus (8 columns: two degree-4 extension
+    // field elements, see `chips::ext`) ===
+    // Closes the gap `chips::program::ProgramChip`'s own doc comment used to
+    // flag: previously every row sent its fetch-decode tuple onto
+    // `Bus::Program` with an unconditional multiplicity of 1 and nothing
+    // ever received it, so a prover could claim any `(pc, opcode, ...)`
+    // tuple at all. See `chips::ext::fingerprint_n` and `machine`.
+    /// Inverse, in the degree-4 extension, of this row's program-bus
+    /// fingerprint over `(pc, opcode, rs1, rs2, rd, imm, funct)`, witnessed
+    /// so the `program_bus_phi` update below avoids an in-circuit division.
+    pub program_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the send side of the
+    /// CPU<->Program LogUp bus. Padding (NOP) rows contribute nothing, the
+    /// same gating the register bus above uses. `machine::ZkIrMachine`
+    /// checks this sums to zero against `ProgramColumns::phi`.
+    pub program_bus_phi: [T; 4],
+
+    // === Cross-chip shift-power LogUp bus (8 columns: two degree-4
+    // extension field elements, see `chips::ext`) ===
+    // Closes the gap `chips::shift::ShiftPowChip`'s own doc comment used to
+    // flag: previously nothing received this chip's `Bus::ShiftPow` send, so
+    // a prover could witness any `pow` at all for a shift, not just
+    // `2^shift_amount`.
+    /// Inverse, in the degree-4 extension, of this row's shift-power bus
+    /// fingerprint over `(shift_amount, shift_pow)`.
+    pub shift_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the send side of the
+    /// CPU<->ShiftPow LogUp bus, gated by `is_shift`. `machine::ZkIrMachine`
+    /// checks this sums to zero against `ShiftPowColumns::phi`.
+    pub shift_bus_phi: [T; 4],
+
+    // === Cross-chip range-check LogUp bus (12 columns: three degree-4
+    // extension field elements, see `chips::ext`) ===
+    // Closes a gap left by this chip's own `shift_remainder_lo`/`_hi` and
+    // `shift_overflow_lo`/`_hi`: `generate_traces` used to only tally these
+    // in plain Rust and hand the tally to `RangeCheckChip::generate_trace`,
+    // which makes `RangeCheckChip`'s own closure check vacuous from this
+    // chip's side -- nothing in this chip's AIR actually committed to having
+    // sent those values. `chips::memory::MemoryColumns::range_bus_phi` is
+    // the pattern this mirrors: every shift row sends exactly two limbs (the
+    // remainder pair or the overflow pair, selected by `is_right_shift`), so
+    // there are two witnessed fingerprint inverses feeding one running sum,
+    // the same two-touches-per-row shape `reg_bus_phi` uses for three.
+    /// Inverse, in the degree-4 extension, of this row's range-check-bus
+    /// fingerprint over its low limb (`shift_remainder_lo` or
+    /// `shift_overflow_lo`, selected by `is_right_shift`).
+    pub range_bus_f_inv_lo: [T; 4],
+    /// Inverse, in the degree-4 extension, of this row's range-check-bus
+    /// fingerprint over its high limb (`shift_remainder_hi` or
+    /// `shift_overflow_hi`).
+    pub range_bus_f_inv_hi: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` summed over this row's two limb touches -- the send
+    /// side of this chip's contribution to `Bus::RangeCheck16`, gated by
+    /// `is_shift`. `machine::ZkIrMachine` checks the four-way sum of this,
+    /// `MemoryColumns::range_bus_phi`, `AluColumns::range_bus_phi`, and
+    /// `RangeCheckColumns::phi` is zero.
+    pub range_bus_phi: [T; 4],
 }
 
 /// Number of columns in the CPU trace
-pub const CPU_NUM_COLUMNS: usize = 32;
+pub const CPU_NUM_COLUMNS: usize = 120;
 
 impl<T> CpuColumns<T> {
     /// Number of columns in the CPU trace
@@ -95,7 +299,7 @@ impl<T> CpuColumns<T> {
 
 impl<T: Copy> CpuColumns<T> {
     /// Get all opcode flag columns as a slice
-    pub fn opcode_flags(&self) -> [T; 12] {
+    pub fn opcode_flags(&self) -> [T; 14] {
         [
             self.is_alu,
             self.is_alu_imm,
@@ -109,6 +313,8 @@ impl<T: Copy> CpuColumns<T> {
             self.is_zk_io,
             self.is_halt,
             self.is_nop,
+            self.is_float,
+            self.is_shift,
         ]
     }
 }
@@ -152,4 +358,18 @@ pub enum AluOp {
     Divu = 12,
     Rem = 13,
     Remu = 14,
+
+    // RV32F (single-precision floating point)
+    FAdd = 15,
+    FSub = 16,
+    FMul = 17,
+    FDiv = 18,
+    FSqrt = 19,
+    FMin = 20,
+    FMax = 21,
+    FCmp = 22,
+    /// FCVT.W.S / FCVT.WU.S: float -> int
+    FcvtWS = 23,
+    /// FCVT.S.W / FCVT.S.WU: int -> float
+    FcvtSW = 24,
 }
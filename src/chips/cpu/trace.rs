@@ -1,11 +1,15 @@
 //! CPU trace generation from execution trace
 
-use std::borrow::BorrowMut;
+use std::borrow::{Borrow, BorrowMut};
 
-use p3_field::Field;
+use p3_field::{Field, PrimeField32};
 use p3_matrix::dense::RowMajorMatrix;
 
 use super::columns::{CpuColumns, CPU_NUM_COLUMNS};
+use crate::chips::ext::{ext_add, ext_from_base, ext_inverse, fingerprint, fingerprint_n};
+use crate::chips::interaction::bus_challenges;
+use crate::chips::range::{self, decompose_u32};
+use crate::chips::shift::SHIFT_TABLE_SIZE;
 use crate::trace::{ExecutionTrace, Step};
 
 /// Opcode constants matching ZK IR spec
@@ -31,30 +35,375 @@ pub mod opcodes {
     pub const OP_ZK_CUSTOM: u8 = 0b0001011;
     pub const OP_ZK_IO: u8 = 0b0101011;
     pub const OP_HALT: u8 = 0b1111111;
+
+    // RV32F (single-precision floating point)
+    /// FADD.S, FSUB.S, FMUL.S, FDIV.S, FSQRT.S, FMIN.S, FMAX.S, FEQ/FLT/FLE.S,
+    /// FCVT.W.S, FCVT.S.W (funct7 disambiguates)
+    pub const OP_FP: u8 = 0b1010011;
+    pub const OP_FLOAT_LOAD: u8 = 0b0000111; // FLW
+    pub const OP_FLOAT_STORE: u8 = 0b0100111; // FSW
+    // R4-type fused multiply-add majors
+    pub const OP_FMADD: u8 = 0b1000011;
+    pub const OP_FMSUB: u8 = 0b1000111;
+    pub const OP_FNMSUB: u8 = 0b1001011;
+    pub const OP_FNMADD: u8 = 0b1001111;
 }
 
-/// Generate the CPU trace from an execution trace
-pub fn generate_cpu_trace<F: Field>(trace: &ExecutionTrace) -> RowMajorMatrix<F> {
+/// Generate the CPU trace from an execution trace, along with three tallies
+/// other chips' `generate_trace` needs to populate their own `multiplicity`
+/// columns against what this chip actually sent:
+///
+/// - a tally of how many times each entry in `trace.program` was fetched by
+///   a non-padding row (indexed the same way
+///   `chips::program::ProgramChip::generate_trace` lays its table out:
+///   `program[i]` is fetched `multiplicities[i]` times, padded with zeros
+///   out to that chip's own padded trace length);
+/// - a tally, indexed by 16-bit value, of this chip's `Bus::RangeCheck16`
+///   sends (the `shift_remainder`/`shift_overflow` limbs), merged with
+///   `MemoryChip::generate_trace`'s and `AluChip::generate_trace`'s own
+///   tallies by `machine::ZkIrMachine`
+///   before reaching `RangeCheckChip::generate_trace`;
+/// - a tally, indexed by shift amount, of this chip's `Bus::ShiftPow` sends,
+///   for `chips::shift::ShiftPowChip::generate_trace`.
+///
+/// Fixed to `crate::F` rather than generic over `Field`, unlike most
+/// per-chip trace generators: the memory LogUp bus columns below go through
+/// `crate::EF`, which is itself defined over the concrete base field.
+pub fn generate_cpu_trace(
+    trace: &ExecutionTrace,
+) -> (
+    RowMajorMatrix<crate::F>,
+    Vec<u64>,
+    [u64; range::RANGE_CHECK_SIZE],
+    [u64; SHIFT_TABLE_SIZE],
+) {
+    type F = crate::F;
+
     let num_steps = trace.steps.len();
     // Pad to next power of 2
     let trace_len = num_steps.next_power_of_two().max(2);
 
     let mut values = vec![F::ZERO; trace_len * CpuColumns::<F>::NUM_COLUMNS];
 
+    let program_trace_len = trace.program.len().next_power_of_two().max(2);
+    let mut program_multiplicities = vec![0u64; program_trace_len];
+    let mut range_multiplicities = [0u64; range::RANGE_CHECK_SIZE];
+    let mut shift_multiplicities = [0u64; SHIFT_TABLE_SIZE];
+
     for (i, step) in trace.steps.iter().enumerate() {
         let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
         let row = &mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS];
         populate_row_from_step::<F>(row, step, i == num_steps - 1);
+        set_nonce::<F>(row, i);
+
+        let row_arr: &[F; CPU_NUM_COLUMNS] = (&*row).try_into().unwrap();
+        let cols: &CpuColumns<F> = row_arr.borrow();
+        if cols.is_nop == F::ZERO {
+            let idx = (step.pc / 4) as usize;
+            if idx < program_multiplicities.len() {
+                program_multiplicities[idx] += 1;
+            }
+        }
+        if cols.is_shift == F::ONE {
+            let shift_amount = cols.shift_amount.as_canonical_u32() as usize;
+            if shift_amount < shift_multiplicities.len() {
+                shift_multiplicities[shift_amount] += 1;
+            }
+            if cols.is_right_shift == F::ONE {
+                range_multiplicities[cols.shift_remainder_lo.as_canonical_u32() as usize] += 1;
+                range_multiplicities[cols.shift_remainder_hi.as_canonical_u32() as usize] += 1;
+            } else {
+                range_multiplicities[cols.shift_overflow_lo.as_canonical_u32() as usize] += 1;
+                range_multiplicities[cols.shift_overflow_hi.as_canonical_u32() as usize] += 1;
+            }
+        }
     }
 
-    // Fill padding rows with NOP
+    // Fill padding rows with NOP. Nonces stay unique over padding too, since
+    // a repeated NOP row would otherwise send (and cancel) the same zeroed
+    // ALU/memory tuple as some other row.
     for i in num_steps..trace_len {
         let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
         let row = &mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS];
         populate_nop_row::<F>(row, i as u64);
+        set_nonce::<F>(row, i);
     }
 
-    RowMajorMatrix::new(values, CpuColumns::<F>::NUM_COLUMNS)
+    populate_mem_bus_columns(&mut values, trace_len);
+    populate_alu_bus_columns(&mut values, trace_len);
+    populate_fpu_bus_columns(&mut values, trace_len);
+    populate_register_bus_columns(&mut values, trace_len);
+    populate_program_bus_columns(&mut values, trace_len);
+    populate_shift_bus_columns(&mut values, trace_len);
+    populate_range_bus_columns(&mut values, trace_len);
+
+    (
+        RowMajorMatrix::new(values, CpuColumns::<F>::NUM_COLUMNS),
+        program_multiplicities,
+        range_multiplicities,
+        shift_multiplicities,
+    )
+}
+
+/// Fill in the cross-chip memory LogUp bus columns (`mem_bus_f_inv`,
+/// `mem_bus_phi`). This needs its own pass over the finished rows since
+/// `mem_bus_phi` is a running sum over the whole trace, unlike every other
+/// column populated per-row above.
+fn populate_mem_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let f = fingerprint(&beta, cols.mem_addr, cols.cycle, cols.mem_val, cols.mem_is_write);
+        let f = ext_add(&alpha, &f);
+        let f_inv = ext_inverse(f);
+        let multiplicity = cols.is_load + cols.is_store;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * f_inv[j];
+        }
+        cols.mem_bus_f_inv = f_inv;
+        cols.mem_bus_phi = phi;
+    }
+}
+
+/// Fill in the cross-chip ALU LogUp bus columns (`alu_bus_f_inv`,
+/// `alu_bus_phi`), the ALU analogue of `populate_mem_bus_columns` above --
+/// one fingerprint term per row over `(opcode, alu_op, rs1_val, rs2_val,
+/// rd_val, nonce)` via `fingerprint_n`, gated by `is_alu + is_alu_imm`
+/// (every other row contributes nothing). `chips::alu::AluChip` is the
+/// receive side this closes against.
+fn populate_alu_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let values_tuple = [cols.opcode, cols.alu_op, cols.rs1_val, cols.rs2_val, cols.rd_val, cols.nonce];
+        let f = ext_add(&alpha, &fingerprint_n(&beta, &values_tuple));
+        let f_inv = ext_inverse(f);
+        let multiplicity = cols.is_alu + cols.is_alu_imm;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * f_inv[j];
+        }
+        cols.alu_bus_f_inv = f_inv;
+        cols.alu_bus_phi = phi;
+    }
+}
+
+/// Fill in the cross-chip FPU LogUp bus columns (`fpu_bus_f_inv`,
+/// `fpu_bus_phi`), the FPU analogue of `populate_alu_bus_columns` above --
+/// one fingerprint term per row over `(funct, rs1_val, rs2_val, rd_val,
+/// nonce)` via `fingerprint_n`, gated by `is_float` (every other row
+/// contributes nothing). `chips::fpu::FpuChip` is the receive side this
+/// closes against.
+fn populate_fpu_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let values_tuple = [cols.funct, cols.rs1_val, cols.rs2_val, cols.rd_val, cols.nonce];
+        let f = ext_add(&alpha, &fingerprint_n(&beta, &values_tuple));
+        let f_inv = ext_inverse(f);
+        let multiplicity = cols.is_float;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * f_inv[j];
+        }
+        cols.fpu_bus_f_inv = f_inv;
+        cols.fpu_bus_phi = phi;
+    }
+}
+
+/// Fill in the cross-chip register LogUp bus columns
+/// (`reg_rs1_bus_f_inv`/`reg_rs2_bus_f_inv`/`reg_rd_bus_f_inv`,
+/// `reg_bus_phi`), the register analogue of `populate_mem_bus_columns`
+/// above -- except each row contributes three fingerprint terms (an `rs1`
+/// read, an `rs2` read, an `rd` write) instead of one, all sharing a
+/// `seq = cycle*3 + slot` sequence number (see `RegisterAccess::cycle`).
+fn populate_register_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let cycle3 = cols.cycle * F::from_canonical_u32(3);
+        let multiplicity = F::ONE - cols.is_nop;
+
+        let f_rs1 = ext_add(&alpha, &fingerprint(&beta, cols.rs1, cycle3, cols.rs1_val, F::ZERO));
+        let f_rs1_inv = ext_inverse(f_rs1);
+
+        let f_rs2 = ext_add(
+            &alpha,
+            &fingerprint(&beta, cols.rs2, cycle3 + F::ONE, cols.rs2_val, F::ZERO),
+        );
+        let f_rs2_inv = ext_inverse(f_rs2);
+
+        let f_rd = ext_add(
+            &alpha,
+            &fingerprint(&beta, cols.rd, cycle3 + F::from_canonical_u32(2), cols.rd_val, F::ONE),
+        );
+        let f_rd_inv = ext_inverse(f_rd);
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * (f_rs1_inv[j] + f_rs2_inv[j] + f_rd_inv[j]);
+        }
+        cols.reg_rs1_bus_f_inv = f_rs1_inv;
+        cols.reg_rs2_bus_f_inv = f_rs2_inv;
+        cols.reg_rd_bus_f_inv = f_rd_inv;
+        cols.reg_bus_phi = phi;
+    }
+}
+
+/// Fill in the cross-chip shift-power LogUp bus columns (`shift_bus_f_inv`,
+/// `shift_bus_phi`), the shift-power analogue of `populate_mem_bus_columns`
+/// above -- one fingerprint term per row over `(shift_amount, pow)`, gated
+/// by `is_shift` (every non-shift row contributes nothing).
+fn populate_shift_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let f = ext_add(&alpha, &fingerprint_n(&beta, &[cols.shift_amount, cols.shift_pow]));
+        let f_inv = ext_inverse(f);
+        let multiplicity = cols.is_shift;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * f_inv[j];
+        }
+        cols.shift_bus_f_inv = f_inv;
+        cols.shift_bus_phi = phi;
+    }
+}
+
+/// Fill in the cross-chip program LogUp bus columns (`program_bus_f_inv`,
+/// `program_bus_phi`), the program analogue of `populate_mem_bus_columns`
+/// above -- one fingerprint term per row, over the full `(pc, opcode, rs1,
+/// rs2, rd, imm, funct)` tuple via `fingerprint_n` rather than `fingerprint`'s
+/// fixed 4 terms, gated the same way the register bus above is (nothing on
+/// padding rows).
+fn populate_program_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+    let beta = bus_beta.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let values_tuple = [cols.pc, cols.opcode, cols.rs1, cols.rs2, cols.rd, cols.imm, cols.funct];
+        let f = ext_add(&alpha, &fingerprint_n(&beta, &values_tuple));
+        let f_inv = ext_inverse(f);
+        let multiplicity = F::ONE - cols.is_nop;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * f_inv[j];
+        }
+        cols.program_bus_f_inv = f_inv;
+        cols.program_bus_phi = phi;
+    }
+}
+
+/// Fill in this chip's own send-side `Bus::RangeCheck16` LogUp bus columns
+/// (`range_bus_f_inv_lo`/`_hi`, `range_bus_phi`) -- the real closure for the
+/// `shift_remainder_lo`/`_hi`/`shift_overflow_lo`/`_hi` limbs
+/// `range_multiplicities` above only tallies in plain Rust, mirroring
+/// `chips::memory`'s `range_bus_phi` for its own address/cycle gap limbs.
+fn populate_range_bus_columns(values: &mut [crate::F], trace_len: usize) {
+    type F = crate::F;
+
+    let (bus_alpha, _bus_beta) = bus_challenges();
+    let alpha = bus_alpha.map(F::from_canonical_u32);
+
+    let mut phi = [F::ZERO; 4];
+    for i in 0..trace_len {
+        let row_offset = i * CpuColumns::<F>::NUM_COLUMNS;
+        let row: &mut [F; CPU_NUM_COLUMNS] =
+            (&mut values[row_offset..row_offset + CpuColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+        let cols: &mut CpuColumns<F> = row.borrow_mut();
+
+        let (lo_val, hi_val) = if cols.is_right_shift == F::ONE {
+            (cols.shift_remainder_lo, cols.shift_remainder_hi)
+        } else {
+            (cols.shift_overflow_lo, cols.shift_overflow_hi)
+        };
+
+        let f_lo = ext_add(&alpha, &ext_from_base(lo_val));
+        let f_hi = ext_add(&alpha, &ext_from_base(hi_val));
+        let f_inv_lo = ext_inverse(f_lo);
+        let f_inv_hi = ext_inverse(f_hi);
+        let multiplicity = cols.is_shift;
+
+        for j in 0..4 {
+            phi[j] = phi[j] + multiplicity * (f_inv_lo[j] + f_inv_hi[j]);
+        }
+        cols.range_bus_f_inv_lo = f_inv_lo;
+        cols.range_bus_f_inv_hi = f_inv_hi;
+        cols.range_bus_phi = phi;
+    }
 }
 
 fn populate_row_from_step<F: Field>(row: &mut [F], step: &Step, is_last: bool) {
@@ -81,8 +430,18 @@ fn populate_row_from_step<F: Field>(row: &mut [F], step: &Step, is_last: bool) {
     // Set opcode flags (one-hot)
     reset_flags(cols);
     match step.opcode {
-        opcodes::OP_ALU => cols.is_alu = F::ONE,
-        opcodes::OP_ALU_IMM => cols.is_alu_imm = F::ONE,
+        opcodes::OP_ALU | opcodes::OP_ALU_IMM if decode_shift_funct(step.funct).is_some() => {
+            let (is_right, is_arith) = decode_shift_funct(step.funct).unwrap();
+            populate_shift_columns(cols, step, is_right, is_arith);
+        }
+        opcodes::OP_ALU => {
+            cols.is_alu = F::ONE;
+            cols.alu_op = F::from_canonical_u8(decode_alu_funct(step.funct, false) as u8);
+        }
+        opcodes::OP_ALU_IMM => {
+            cols.is_alu_imm = F::ONE;
+            cols.alu_op = F::from_canonical_u8(decode_alu_funct(step.funct, true) as u8);
+        }
         opcodes::OP_BRANCH => cols.is_branch = F::ONE,
         opcodes::OP_JAL | opcodes::OP_JALR => cols.is_jump = F::ONE,
         opcodes::OP_LOAD => cols.is_load = F::ONE,
@@ -95,15 +454,38 @@ fn populate_row_from_step<F: Field>(row: &mut [F], step: &Step, is_last: bool) {
             cols.is_halt = F::ONE;
             cols.is_halted = F::ONE;
         }
+        opcodes::OP_FLOAT_LOAD => cols.is_load = F::ONE,
+        opcodes::OP_FLOAT_STORE => cols.is_store = F::ONE,
+        opcodes::OP_FP => {
+            cols.is_float = F::ONE;
+            // Bits 3..6 of `step.funct` are this chip's own packed stand-in
+            // for RV32F's real `funct7` (see `chips::fpu`'s
+            // `FP_GROUP_*`/`decode_fp_op`, which this mirrors rather than
+            // calls -- `chips::fpu` isn't reachable from here any more than
+            // `cpu::trace` is reachable from it).
+            match (step.funct >> 3) & 0b1111 {
+                0 => cols.is_fp_add = F::ONE,
+                1 => cols.is_fp_mul = F::ONE,
+                2 => cols.is_fp_cmp = F::ONE,
+                3 => cols.is_fp_convert = F::ONE,
+                _ => {}
+            }
+        }
+        opcodes::OP_FMADD | opcodes::OP_FMSUB | opcodes::OP_FNMSUB | opcodes::OP_FNMADD => {
+            cols.is_float = F::ONE;
+            cols.is_fp_fma = F::ONE;
+        }
         _ => cols.is_nop = F::ONE,
     }
 
     // Compute next_pc (simplified - actual implementation would check all cases)
     cols.next_pc = F::from_canonical_u32(step.pc.wrapping_add(4));
 
-    // ALU operation (would need to decode from funct)
-    cols.alu_op = F::ZERO; // Placeholder
-    cols.alu_result = cols.rd_val; // Simplified
+    // `alu_op` is set above for `is_alu`/`is_alu_imm` rows (`OP_FP` rows
+    // select their sub-op through the `is_fp_*` flags instead); every
+    // other row leaves it at its `reset_flags` default of zero, meaningless
+    // since nothing reads it off those rows.
+    cols.alu_result = cols.rd_val; // Trusted from `step.registers`; `chips::alu::AluChip` is what actually recomputes and binds this for `is_alu`/`is_alu_imm` rows.
 
     // Handle halt
     if is_last || step.opcode == opcodes::OP_HALT {
@@ -112,6 +494,100 @@ fn populate_row_from_step<F: Field>(row: &mut [F], step: &Step, is_last: bool) {
     }
 }
 
+/// Shift funct3/funct7 encodings, pinning down the otherwise-unspecified
+/// "funct3 + funct7 combined" packing of `step.funct` (see
+/// `CpuColumns::funct`'s doc comment) for exactly the one case this chip
+/// needs to read it: `funct3` in the low 3 bits, plus the single bit of
+/// `funct7` that actually varies across RV32I's shifts (bit 5, the
+/// SRL/SRA switch) carried as bit 3.
+const SHIFT_FUNCT3_SLL: u8 = 0b001;
+const SHIFT_FUNCT3_SR: u8 = 0b101;
+const SHIFT_FUNCT_ARITH_BIT: u8 = 0b1000;
+
+/// If `funct` encodes SLL/SLLI, SRL/SRLI, or SRA/SRAI, returns
+/// `(is_right_shift, is_arith_shift)`.
+fn decode_shift_funct(funct: u8) -> Option<(bool, bool)> {
+    match funct & 0b111 {
+        SHIFT_FUNCT3_SLL => Some((false, false)),
+        SHIFT_FUNCT3_SR => Some((true, funct & SHIFT_FUNCT_ARITH_BIT != 0)),
+        _ => None,
+    }
+}
+
+/// Non-shift ALU funct3 values, same packing convention `decode_shift_funct`
+/// above documents (low 3 bits funct3, bit 3 the one varying funct7 bit).
+const ALU_FUNCT3_ADD_SUB: u8 = 0b000;
+const ALU_FUNCT3_SLT: u8 = 0b010;
+const ALU_FUNCT3_SLTU: u8 = 0b011;
+const ALU_FUNCT3_XOR: u8 = 0b100;
+const ALU_FUNCT3_OR: u8 = 0b110;
+const ALU_FUNCT3_AND: u8 = 0b111;
+
+/// Decode an `OP_ALU`/`OP_ALU_IMM` row's `funct` into the `AluOp` variant
+/// `chips::alu::AluChip` recomputes and binds to `rd_val`. Only called on
+/// rows `decode_shift_funct` already ruled out, so `funct3` is always one of
+/// the six values below. `is_imm` suppresses the SUB/ADD split for I-type
+/// rows: RV32I only has ADDI, never SUBI, so the funct7 bit this chip reuses
+/// for SUB is meaningless (left zero) there.
+fn decode_alu_funct(funct: u8, is_imm: bool) -> super::columns::AluOp {
+    use super::columns::AluOp;
+    match funct & 0b111 {
+        ALU_FUNCT3_ADD_SUB => {
+            if !is_imm && funct & SHIFT_FUNCT_ARITH_BIT != 0 {
+                AluOp::Sub
+            } else {
+                AluOp::Add
+            }
+        }
+        ALU_FUNCT3_SLT => AluOp::Slt,
+        ALU_FUNCT3_SLTU => AluOp::Sltu,
+        ALU_FUNCT3_XOR => AluOp::Xor,
+        ALU_FUNCT3_OR => AluOp::Or,
+        ALU_FUNCT3_AND => AluOp::And,
+        _ => AluOp::Add,
+    }
+}
+
+/// Populate the shift sub-selectors and witness columns for an SLL/SRL/SRA
+/// row (see the SLL multiply / SRL+SRA divide constraints in `air.rs`).
+/// `rd_val`/`alu_result` are left to the generic "trust `step.registers`'s
+/// post-state" handling below, same as every other ALU op -- only the
+/// witnesses the shift constraint itself needs are set here: `shift_overflow`
+/// for SLL (the high limb the truncated 32-bit result sheds), or
+/// `shift_remainder` for SRL/SRA.
+fn populate_shift_columns<F: Field>(cols: &mut CpuColumns<F>, step: &Step, is_right: bool, is_arith: bool) {
+    let rs1_raw = step.registers[step.rs1 as usize];
+    let shift_amount_raw: u32 = if step.opcode == opcodes::OP_ALU_IMM {
+        (step.imm as u32) & 0x1F
+    } else {
+        step.registers[step.rs2 as usize] & 0x1F
+    };
+    let pow_raw: u64 = 1u64 << shift_amount_raw;
+
+    cols.is_shift = F::ONE;
+    cols.is_right_shift = if is_right { F::ONE } else { F::ZERO };
+    cols.is_arith_shift = if is_arith { F::ONE } else { F::ZERO };
+    cols.shift_amount = F::from_canonical_u32(shift_amount_raw);
+    cols.shift_pow = F::from_wrapped_u64(pow_raw);
+    cols.sign_bit = F::from_canonical_u32((rs1_raw >> 31) & 1);
+
+    if is_right {
+        let remainder_raw = rs1_raw & (pow_raw as u32).wrapping_sub(1);
+        let (lo, hi) = decompose_u32(remainder_raw);
+        cols.shift_remainder = F::from_canonical_u32(remainder_raw);
+        cols.shift_remainder_lo = F::from_canonical_u32(lo);
+        cols.shift_remainder_hi = F::from_canonical_u32(hi);
+        cols.shift_pow_inv = cols.shift_pow.inverse();
+    } else {
+        let product_raw: u64 = (rs1_raw as u64) * pow_raw;
+        let overflow_raw = (product_raw >> 32) as u32;
+        let (lo, hi) = decompose_u32(overflow_raw);
+        cols.shift_overflow = F::from_canonical_u32(overflow_raw);
+        cols.shift_overflow_lo = F::from_canonical_u32(lo);
+        cols.shift_overflow_hi = F::from_canonical_u32(hi);
+    }
+}
+
 fn populate_nop_row<F: Field>(row: &mut [F], cycle: u64) {
     let row_arr: &mut [F; CPU_NUM_COLUMNS] = row.try_into().unwrap();
     let cols: &mut CpuColumns<F> = row_arr.borrow_mut();
@@ -121,6 +597,12 @@ fn populate_nop_row<F: Field>(row: &mut [F], cycle: u64) {
     cols.is_nop = F::ONE;
 }
 
+fn set_nonce<F: Field>(row: &mut [F], index: usize) {
+    let row_arr: &mut [F; CPU_NUM_COLUMNS] = row.try_into().unwrap();
+    let cols: &mut CpuColumns<F> = row_arr.borrow_mut();
+    cols.nonce = F::from_canonical_usize(index);
+}
+
 fn reset_flags<F: Field>(cols: &mut CpuColumns<F>) {
     cols.is_alu = F::ZERO;
     cols.is_alu_imm = F::ZERO;
@@ -134,4 +616,23 @@ fn reset_flags<F: Field>(cols: &mut CpuColumns<F>) {
     cols.is_zk_io = F::ZERO;
     cols.is_halt = F::ZERO;
     cols.is_nop = F::ZERO;
+    cols.is_float = F::ZERO;
+    cols.is_fp_add = F::ZERO;
+    cols.is_fp_mul = F::ZERO;
+    cols.is_fp_fma = F::ZERO;
+    cols.is_fp_cmp = F::ZERO;
+    cols.is_fp_convert = F::ZERO;
+    cols.is_shift = F::ZERO;
+    cols.is_right_shift = F::ZERO;
+    cols.is_arith_shift = F::ZERO;
+    cols.shift_amount = F::ZERO;
+    cols.shift_pow = F::ZERO;
+    cols.shift_pow_inv = F::ZERO;
+    cols.shift_remainder = F::ZERO;
+    cols.shift_remainder_lo = F::ZERO;
+    cols.shift_remainder_hi = F::ZERO;
+    cols.shift_overflow = F::ZERO;
+    cols.shift_overflow_lo = F::ZERO;
+    cols.shift_overflow_hi = F::ZERO;
+    cols.sign_bit = F::ZERO;
 }
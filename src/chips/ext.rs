@@ -0,0 +1,130 @@
+//! Shared degree-4 binomial extension field arithmetic.
+//!
+//! Baby Bear is only ~31 bits, far too small for a sound permutation/LogUp
+//! argument built over a single base-field challenge (a malicious prover
+//! could search for a fingerprint collision in ~2^31 tries). Every chip that
+//! runs such an argument -- the memory chip's own sorted/exec permutation,
+//! and the cross-chip bus in `machine` -- draws its challenges from, and
+//! accumulates its running sum in, the degree-4 extension `EF = GF(p^4)`
+//! with irreducible polynomial `x^4 - 11` (`crate::EF`), for ~2^-124 error
+//! instead of ~2^-31.
+//!
+//! Trace columns can only ever hold base-field values, so an extension
+//! element is represented on the trace as 4 adjacent base-field columns, and
+//! the functions below implement `x^4 - 11` arithmetic generically over
+//! `FieldAlgebra` so the exact same code computes the trace (`T = F`) and
+//! checks it in-circuit (`T = AB::Expr`). Inversion is the one operation
+//! that's only meaningful off-circuit: there's no cheap polynomial way to
+//! compute a field inverse, so `ext_inverse` leans on `crate::EF`'s real
+//! `Field::inverse` impl during trace generation, and in-circuit the witness
+//! just gets checked with an `ext_mul` against `ext_one`.
+
+use p3_field::{AbstractExtensionField, Field, FieldAlgebra, PrimeField32};
+
+use crate::{EF, F};
+
+/// The non-residue for Baby Bear's degree-4 binomial extension: `x^4 - 11`
+/// is irreducible over `F_p`.
+pub const EXT_W: u32 = 11;
+
+/// The extension-field multiplicative identity, as base-field coordinates.
+pub fn ext_one<T: FieldAlgebra>() -> [T; 4] {
+    [T::ONE, T::ZERO, T::ZERO, T::ZERO]
+}
+
+/// Embed a base-field value as a degree-4 extension element.
+pub fn ext_from_base<T: FieldAlgebra>(v: T) -> [T; 4] {
+    [v, T::ZERO, T::ZERO, T::ZERO]
+}
+
+/// Add two degree-4 extension elements component-wise.
+pub fn ext_add<T: FieldAlgebra>(a: &[T; 4], b: &[T; 4]) -> [T; 4] {
+    [
+        a[0].clone() + b[0].clone(),
+        a[1].clone() + b[1].clone(),
+        a[2].clone() + b[2].clone(),
+        a[3].clone() + b[3].clone(),
+    ]
+}
+
+/// Subtract two degree-4 extension elements component-wise.
+pub fn ext_sub<T: FieldAlgebra>(a: &[T; 4], b: &[T; 4]) -> [T; 4] {
+    [
+        a[0].clone() - b[0].clone(),
+        a[1].clone() - b[1].clone(),
+        a[2].clone() - b[2].clone(),
+        a[3].clone() - b[3].clone(),
+    ]
+}
+
+/// Multiply two degree-4 extension elements `a = a0 + a1*x + a2*x^2 + a3*x^3`
+/// and `b` modulo `x^4 - W`, i.e. with `x^4` reduced to `W`.
+pub fn ext_mul<T: FieldAlgebra>(a: &[T; 4], b: &[T; 4]) -> [T; 4] {
+    let w = T::from_canonical_u32(EXT_W);
+    let c0 = a[0].clone() * b[0].clone()
+        + w.clone()
+            * (a[1].clone() * b[3].clone() + a[2].clone() * b[2].clone() + a[3].clone() * b[1].clone());
+    let c1 = a[0].clone() * b[1].clone() + a[1].clone() * b[0].clone()
+        + w.clone() * (a[2].clone() * b[3].clone() + a[3].clone() * b[2].clone());
+    let c2 = a[0].clone() * b[2].clone() + a[1].clone() * b[1].clone() + a[2].clone() * b[0].clone()
+        + w * (a[3].clone() * b[3].clone());
+    let c3 = a[0].clone() * b[3].clone() + a[1].clone() * b[2].clone() + a[2].clone() * b[1].clone()
+        + a[3].clone() * b[0].clone();
+    [c0, c1, c2, c3]
+}
+
+/// Fingerprint one `(address, cycle, value, is_write)` tuple into the
+/// extension field: `f = address + beta*cycle + beta^2*value +
+/// beta^3*is_write`. Callers add the verifier challenge `alpha` themselves
+/// (via `ext_add`), since some arguments use `alpha - f` (grand product) and
+/// others use `alpha + f` (LogUp).
+pub fn fingerprint<T: FieldAlgebra>(beta: &[T; 4], address: T, cycle: T, value: T, is_write: T) -> [T; 4] {
+    let beta2 = ext_mul(beta, beta);
+    let beta3 = ext_mul(&beta2, beta);
+    let mut f = ext_from_base(address);
+    f = ext_add(&f, &ext_mul(beta, &ext_from_base(cycle)));
+    f = ext_add(&f, &ext_mul(&beta2, &ext_from_base(value)));
+    f = ext_add(&f, &ext_mul(&beta3, &ext_from_base(is_write)));
+    f
+}
+
+/// Fingerprint an arbitrary-length tuple into the extension field: `f =
+/// values[0] + beta*values[1] + beta^2*values[2] + ...`. The general form of
+/// `fingerprint` above, for buses whose tuple doesn't fit that function's
+/// fixed 4-term shape (see `chips::program`'s 7-value `Bus::Program` tuple).
+/// Callers add the verifier challenge `alpha` themselves, same convention as
+/// `fingerprint`.
+pub fn fingerprint_n<T: FieldAlgebra>(beta: &[T; 4], values: &[T]) -> [T; 4] {
+    let mut f = ext_from_base(T::ZERO);
+    let mut power = ext_one::<T>();
+    for v in values {
+        f = ext_add(&f, &ext_mul(&power, &ext_from_base(v.clone())));
+        power = ext_mul(&power, beta);
+    }
+    f
+}
+
+/// Decompose a `crate::EF` value into its 4 base-field coordinates, for
+/// threading a Fiat-Shamir challenge into a chip's per-row column
+/// arithmetic.
+pub fn ext_to_coords(e: EF) -> [F; 4] {
+    let coords = e.as_base_slice();
+    [coords[0], coords[1], coords[2], coords[3]]
+}
+
+/// Decompose a `crate::EF` value into the coordinates of a generic
+/// `FieldAlgebra`, for using a challenge directly inside `ext_mul`/`ext_add`
+/// at either trace-generation or constraint-evaluation time.
+pub fn ext_to_generic<T: FieldAlgebra>(e: EF) -> [T; 4] {
+    ext_to_coords(e).map(|c| T::from_canonical_u32(c.as_canonical_u32()))
+}
+
+/// Invert a degree-4 extension element given as base-field coordinates,
+/// using `crate::EF`'s real field inversion. Off-circuit only (trace
+/// generation): in-circuit, the inverse is a witness checked with a single
+/// `ext_mul` against `ext_one`, never computed from scratch.
+pub fn ext_inverse(a: [F; 4]) -> [F; 4] {
+    let a_ext = EF::from_base_slice(&a);
+    let inv_ext = a_ext.inverse();
+    ext_to_coords(inv_ext)
+}
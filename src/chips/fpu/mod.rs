@@ -0,0 +1,605 @@
+//! FPU Chip: RV32F single-precision floating-point arithmetic
+//!
+//! IEEE-754 arithmetic (exponent alignment, mantissa multiplication,
+//! normalization, rounding) isn't expressible as a single field constraint,
+//! so the CPU chip routes every RV32F op here over `Bus::Fpu` instead of
+//! constraining it inline the way it does integer ALU ops. Each operand is
+//! witnessed pre-decomposed into sign/exponent/mantissa so the constraints
+//! operate on those pieces directly rather than reconstructing them from a
+//! packed 32-bit float.
+//!
+//! This chip concretely constrains the multiply path (mantissa product), the
+//! round-to-nearest-even decision from witnessed guard/round/sticky bits,
+//! and the FEQ.S/FLT.S/FLE.S compare path (via the standard "flip the sign
+//! bit, or invert the whole pattern if negative" trick that turns IEEE-754
+//! ordering into an unsigned integer comparison of the raw bit pattern,
+//! witnessed the same way `chips::alu::AluChip` witnesses its SUB/SLTU
+//! borrow bit). Add, fused multiply-add, divide, sqrt, and int<->float
+//! conversion share the same column layout (selected by `is_add_op`/
+//! `is_mul_op`/the implicit "neither" case) but their own constraints are
+//! deferred -- exponent alignment for add and the iterative/long-division
+//! structure of div/sqrt are both substantially more machinery than fits
+//! here, the same way `Sha256Chip` only lays out columns today without
+//! constraining them.
+//!
+//! Receives `Bus::Fpu`'s `(funct, rs1_val, rs2_val, rd_val, nonce)` tuple --
+//! row-aligned 1:1 with `chips::cpu::trace::generate_cpu_trace`'s trace
+//! (same `trace_len`, same per-row `nonce = row index`), with `is_real`
+//! marking every row the CPU counted in `fpu_multiplicity` (i.e. every
+//! `is_float` row, not just the add/mul/cmp subset this chip actually
+//! constrains) -- the same "receive side must match the sender's
+//! multiplicity exactly or the bus doesn't close" requirement
+//! `chips::alu::AluChip`'s own doc comment explains.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_inverse, ext_mul, fingerprint_n};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::trace::ExecutionTrace;
+
+/// Number of bits in one RV32F operand, decomposed the same way
+/// `chips::alu::AluChip` decomposes its 32-bit integer operands -- needed
+/// here only to build the compare path's total-order key.
+const FP_OPERAND_BITS: usize = 32;
+
+/// FPU trace columns
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FpuColumns<T> {
+    /// Raw op selector from the CPU's `funct` column (mirrors the bus tuple)
+    pub funct: T,
+    /// This row is an FADD.S/FSUB.S
+    pub is_add_op: T,
+    /// This row is an FMUL.S
+    pub is_mul_op: T,
+
+    // === Operand a, decomposed ===
+    pub sign_a: T,
+    pub exp_a: T,
+    /// 24-bit mantissa including the implicit leading 1 (0 for subnormals)
+    pub mantissa_a: T,
+
+    // === Operand b, decomposed ===
+    pub sign_b: T,
+    pub exp_b: T,
+    pub mantissa_b: T,
+
+    // === Result, decomposed ===
+    pub sign_result: T,
+    pub exp_result: T,
+    pub mantissa_result: T,
+    /// Low bit of the (pre-rounding) mantissa result, used by the
+    /// round-to-nearest-even tie-break
+    pub mantissa_lsb: T,
+
+    /// Unnormalized `mantissa_a * mantissa_b` product (up to 48 bits)
+    pub mantissa_product: T,
+    /// Witnessed left/right normalization shift applied to the raw result
+    pub shift_amount: T,
+
+    // === Rounding (round-to-nearest-even) ===
+    pub guard_bit: T,
+    pub round_bit: T,
+    pub sticky_bit: T,
+    /// 1 if rounding rounds the mantissa up
+    pub round_up: T,
+
+    /// Row-unique nonce matching the CPU row that sent this tuple
+    pub nonce: T,
+
+    // === Cross-chip FPU LogUp bus (receive side, 4 columns before the
+    // 8-column phi-accumulator below, see the module doc comment) ===
+    /// Packed 32-bit bit pattern of operand a, as sent over `Bus::Fpu`'s
+    /// `rs1_val`. Bound to `sign_a`/the low 31 bits of `rs1_bits` below.
+    pub raw_rs1: T,
+    /// Packed 32-bit bit pattern of operand b (`Bus::Fpu`'s `rs2_val`).
+    pub raw_rs2: T,
+    /// Packed 32-bit bit pattern of the result (`Bus::Fpu`'s `rd_val`) --
+    /// for the compare ops this is the 0/1 integer result, not a float.
+    pub raw_rd: T,
+    /// 1 for a genuine `is_float` row (see `fpu_multiplicity` in
+    /// `chips::cpu::air`), 0 for power-of-two padding. Unlike
+    /// `is_add_op`/`is_mul_op`/`is_cmp_op`, this is set for every RV32F row
+    /// this chip receives, including the FMA/convert ops it doesn't
+    /// constrain yet -- it has to be, or the bus multiplicities on the two
+    /// sides of `machine::check_fpu_bus_closure` wouldn't match.
+    pub is_real: T,
+
+    // === Compare sub-op (FEQ.S/FLT.S/FLE.S), one-hot when set (4 columns) ===
+    /// This row is one of FEQ.S/FLT.S/FLE.S.
+    pub is_cmp_op: T,
+    pub is_feq: T,
+    pub is_flt: T,
+    pub is_fle: T,
+
+    // === Bit decompositions of the raw operands (64 columns), used only by
+    // the compare path's total-order key below ===
+    pub rs1_bits: [T; FP_OPERAND_BITS],
+    pub rs2_bits: [T; FP_OPERAND_BITS],
+
+    // === Compare ordering witnesses (4 columns) ===
+    /// `key(a) - key(b)` as an unsigned 32-bit value (`key` is the
+    /// sign-adjusted bit pattern built in `eval`, the standard trick that
+    /// turns IEEE-754 total order into unsigned integer order):
+    /// `diff_ab = key(a) - key(b) + borrow_ab * 2^32`.
+    pub diff_ab: T,
+    /// 1 iff `key(a) < key(b)`, i.e. `a < b` -- the witness FLT.S's result
+    /// is bound to.
+    pub borrow_ab: T,
+    /// `key(b) - key(a)`, the symmetric witness: `diff_ba = key(b) - key(a)
+    /// + borrow_ba * 2^32`.
+    pub diff_ba: T,
+    /// 1 iff `b < a` -- `1 - borrow_ba` is FLE.S's result, and
+    /// `1 - borrow_ab - borrow_ba` is FEQ.S's (exactly one of `a<b`, `b<a`,
+    /// `a==b` holds for distinct, non-NaN keys; NaN/signed-zero edge cases
+    /// aren't specially handled, the same "meaningful subset, not
+    /// exhaustive" scope the module doc comment describes).
+    pub borrow_ba: T,
+
+    // === Cross-chip FPU LogUp bus (8 columns, degree-4 extension, see
+    // `machine::check_fpu_bus_closure`) ===
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// over `(funct, raw_rs1, raw_rs2, raw_rd, nonce)`.
+    pub f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `-is_real / fingerprint`
+    /// over this chip's rows -- the receive side of `Bus::Fpu`.
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `chips::cpu::columns::CpuColumns::fpu_bus_phi`.
+    pub phi: [T; 4],
+}
+
+/// Number of columns in the FPU trace.
+pub const FPU_NUM_COLUMNS: usize =
+    3 + 3 + 3 + 3 + 1 + 1 + 1 + 4 + 1 + 3 + 1 + 4 + FP_OPERAND_BITS * 2 + 4 + 4 + 4;
+
+impl<T> FpuColumns<T> {
+    pub const NUM_COLUMNS: usize = FPU_NUM_COLUMNS;
+}
+
+impl<T> Borrow<FpuColumns<T>> for [T; FPU_NUM_COLUMNS] {
+    fn borrow(&self) -> &FpuColumns<T> {
+        unsafe { &*(self.as_ptr() as *const FpuColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<FpuColumns<T>> for [T; FPU_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut FpuColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut FpuColumns<T>) }
+    }
+}
+
+/// FPU Chip for RV32F arithmetic
+pub struct FpuChip;
+
+impl Default for FpuChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FpuChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for FpuChip {
+    fn width(&self) -> usize {
+        FpuColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for FpuChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let local_arr: &[AB::Var; FPU_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let local: &FpuColumns<AB::Var> = local_arr.borrow();
+        let next_slice = main.row_slice(1);
+        let next_arr: &[AB::Var; FPU_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let next: &FpuColumns<AB::Var> = next_arr.borrow();
+
+        // Boolean witnesses. Their actual bit-widths (8-bit exponent,
+        // 24-bit mantissa, etc.) aren't range-checked by this chip, and it
+        // sends nothing onto `Bus::RangeCheck16`.
+        for bit in [
+            local.sign_a,
+            local.sign_b,
+            local.sign_result,
+            local.mantissa_lsb,
+            local.guard_bit,
+            local.round_bit,
+            local.sticky_bit,
+            local.round_up,
+            local.is_add_op,
+            local.is_mul_op,
+            local.is_real,
+            local.is_cmp_op,
+            local.is_feq,
+            local.is_flt,
+            local.is_fle,
+            local.borrow_ab,
+            local.borrow_ba,
+        ] {
+            builder.assert_zero(bit.into() * (AB::Expr::ONE - bit.into()));
+        }
+        builder.assert_zero(
+            local.is_add_op.into() * local.is_mul_op.into(), // mutually exclusive
+        );
+        builder.assert_eq(
+            local.is_cmp_op,
+            local.is_feq.into() + local.is_flt.into() + local.is_fle.into(),
+        );
+
+        // Mantissa multiplication (the FMUL.S path): the unnormalized
+        // product is exactly the product of the two 24-bit mantissas.
+        // Normalizing it (the `shift_amount` witness) and folding that into
+        // `exp_result`/`mantissa_result` is left for the full pipeline --
+        // this pins down the one piece that's a plain field multiplication.
+        builder
+            .when(local.is_mul_op)
+            .assert_eq(local.mantissa_product, local.mantissa_a.into() * local.mantissa_b.into());
+
+        builder
+            .when(local.is_mul_op)
+            .assert_eq(local.sign_result, local.sign_a.into() + local.sign_b.into()
+                - AB::Expr::from_canonical_u32(2) * local.sign_a.into() * local.sign_b.into());
+
+        // Round-to-nearest-even: round up iff the guard bit is set and
+        // either the round or sticky bit is set (clearly more than halfway),
+        // or it's an exact tie (guard set, round and sticky both clear) and
+        // the truncated mantissa's LSB is 1 (ties go to even).
+        let more_than_half: AB::Expr = local.guard_bit.into()
+            * (local.round_bit.into() + local.sticky_bit.into()
+                - local.round_bit.into() * local.sticky_bit.into());
+        let exact_tie: AB::Expr = local.guard_bit.into()
+            * (AB::Expr::ONE - local.round_bit.into())
+            * (AB::Expr::ONE - local.sticky_bit.into());
+        let expected_round_up = more_than_half + exact_tie * local.mantissa_lsb.into();
+
+        builder.assert_eq(local.round_up, expected_round_up);
+
+        // === Bit decompositions of the raw operands, reconstructing
+        // `raw_rs1`/`raw_rs2` and pinning `sign_a`/`sign_b` to their top bit
+        // -- the same shape `chips::alu::AluChip::eval` uses for its
+        // operands ===
+        let mut rs1_reconstructed = AB::Expr::ZERO;
+        let mut rs2_reconstructed = AB::Expr::ZERO;
+        for i in 0..FP_OPERAND_BITS {
+            builder.assert_zero(local.rs1_bits[i].into() * (AB::Expr::ONE - local.rs1_bits[i].into()));
+            builder.assert_zero(local.rs2_bits[i].into() * (AB::Expr::ONE - local.rs2_bits[i].into()));
+            let weight = AB::Expr::from_wrapped_u64(1u64 << i);
+            rs1_reconstructed = rs1_reconstructed + weight.clone() * local.rs1_bits[i].into();
+            rs2_reconstructed = rs2_reconstructed + weight * local.rs2_bits[i].into();
+        }
+        builder.assert_eq(local.raw_rs1, rs1_reconstructed);
+        builder.assert_eq(local.raw_rs2, rs2_reconstructed);
+        builder.assert_eq(local.sign_a, local.rs1_bits[FP_OPERAND_BITS - 1]);
+        builder.assert_eq(local.sign_b, local.rs2_bits[FP_OPERAND_BITS - 1]);
+
+        // === Compare path: FEQ.S/FLT.S/FLE.S via the standard IEEE-754
+        // total-order trick -- XOR every bit of the raw pattern with a mask
+        // that's all-ones for negative numbers (reversing their order) or
+        // just the sign bit for non-negative ones (so positives sort above
+        // negatives), then compare the result as an unsigned integer ===
+        let key = |bits: &[AB::Var; FP_OPERAND_BITS], sign: AB::Expr| -> AB::Expr {
+            let mut acc = AB::Expr::ZERO;
+            for i in 0..FP_OPERAND_BITS - 1 {
+                let bit = bits[i].into();
+                let xor = bit.clone() + sign.clone() - AB::Expr::from_canonical_u32(2) * bit * sign.clone();
+                acc = acc + AB::Expr::from_wrapped_u64(1u64 << i) * xor;
+            }
+            // Top bit is always XORed with the constant 1 (the mask's top
+            // bit is 1 in both the negative and non-negative case).
+            let top_xor = AB::Expr::ONE - sign;
+            acc + AB::Expr::from_wrapped_u64(1u64 << (FP_OPERAND_BITS - 1)) * top_xor
+        };
+        let key_a = key(&local.rs1_bits, local.sign_a.into());
+        let key_b = key(&local.rs2_bits, local.sign_b.into());
+
+        let base32 = AB::Expr::from_canonical_u64(1u64 << 32);
+        builder.when(local.is_cmp_op).assert_eq(
+            local.diff_ab,
+            key_a.clone() - key_b.clone() + local.borrow_ab.into() * base32.clone(),
+        );
+        builder.when(local.is_cmp_op).assert_eq(
+            local.diff_ba,
+            key_b - key_a + local.borrow_ba.into() * base32,
+        );
+
+        builder.when(local.is_flt).assert_eq(local.raw_rd, local.borrow_ab);
+        builder
+            .when(local.is_fle)
+            .assert_eq(local.raw_rd, AB::Expr::ONE - local.borrow_ba.into());
+        builder.when(local.is_feq).assert_eq(
+            local.raw_rd,
+            AB::Expr::ONE - local.borrow_ab.into() - local.borrow_ba.into(),
+        );
+
+        // === Cross-chip FPU LogUp bus (receive side), degree-4 extension
+        // field -- same shape `chips::alu::AluChip::eval` uses for
+        // `Bus::Alu` ===
+        let (alpha, beta) = bus_challenges();
+        let alpha: [AB::Expr; 4] = alpha.map(AB::Expr::from_canonical_u32);
+        let beta: [AB::Expr; 4] = beta.map(AB::Expr::from_canonical_u32);
+        let one: [AB::Expr; 4] = [AB::Expr::ONE, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO];
+
+        let bus_values_local = vec![
+            local.funct.into(),
+            local.raw_rs1.into(),
+            local.raw_rs2.into(),
+            local.raw_rd.into(),
+            local.nonce.into(),
+        ];
+        let f_local = ext_add(&alpha, &fingerprint_n(&beta, &bus_values_local));
+        let f_inv_local: [AB::Expr; 4] = local.f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+        let neg_is_real_local = AB::Expr::ZERO - local.is_real.into();
+        for i in 0..4 {
+            builder.when_first_row().assert_eq(
+                local.phi[i],
+                neg_is_real_local.clone() * f_inv_local[i].clone(),
+            );
+        }
+
+        let bus_values_next = vec![
+            next.funct.into(),
+            next.raw_rs1.into(),
+            next.raw_rs2.into(),
+            next.raw_rd.into(),
+            next.nonce.into(),
+        ];
+        let f_next = ext_add(&alpha, &fingerprint_n(&beta, &bus_values_next));
+        let f_inv_next: [AB::Expr; 4] = next.f_inv.map(Into::into);
+        let check_next = ext_mul(&f_next, &f_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_next[i].clone(), one[i].clone());
+        }
+        let neg_is_real_next = AB::Expr::ZERO - next.is_real.into();
+        for i in 0..4 {
+            let term_next = neg_is_real_next.clone() * f_inv_next[i].clone();
+            builder
+                .when_transition()
+                .assert_eq(next.phi[i].into() - local.phi[i].into(), term_next);
+        }
+    }
+}
+
+impl FpuChip {
+    /// The receive side of `Bus::Fpu`: this row's operand tuple, counted
+    /// `is_real` times (every `is_float` row the CPU sent, not just the
+    /// add/mul/cmp subset this chip constrains -- see `FpuColumns::is_real`).
+    pub fn receives<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &FpuColumns<AB::Var>,
+    ) -> Interaction<AB::Expr> {
+        builder.receive(
+            Bus::Fpu,
+            vec![
+                local.funct.into(),
+                local.raw_rs1.into(),
+                local.raw_rs2.into(),
+                local.raw_rd.into(),
+                local.nonce.into(),
+            ],
+            local.is_real.into(),
+        )
+    }
+
+    /// Generate the FPU trace, row-aligned 1:1 with
+    /// `chips::cpu::trace::generate_cpu_trace`'s trace (same `trace_len`,
+    /// same `nonce = row index`) -- the same shape `chips::alu::AluChip`
+    /// uses, for the same reason: RV32F operand values span the full
+    /// 32-bit domain, so this can't be a small fixed-table receiver.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `AluChip::generate_trace`: the bus columns below go through
+    /// `crate::EF`.
+    pub fn generate_trace(&self, trace: &ExecutionTrace) -> RowMajorMatrix<crate::F> {
+        type F = crate::F;
+
+        let num_steps = trace.steps.len();
+        let trace_len = num_steps.next_power_of_two().max(2);
+        let mut values = vec![F::ZERO; trace_len * FpuColumns::<F>::NUM_COLUMNS];
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let beta = raw_bus_beta.map(F::from_canonical_u32);
+
+        let mut phi = [F::ZERO; 4];
+
+        for i in 0..trace_len {
+            let row_offset = i * FpuColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; FPU_NUM_COLUMNS] =
+                (&mut values[row_offset..row_offset + FpuColumns::<F>::NUM_COLUMNS])
+                    .try_into()
+                    .unwrap();
+            let cols: &mut FpuColumns<F> = row.borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+
+            if let Some(step) = trace.steps.get(i) {
+                if let Some(group) = decode_fp_op(step.opcode, step.funct) {
+                    cols.is_real = F::ONE;
+                    cols.funct = F::from_canonical_u8(step.funct);
+
+                    let rs1_raw = step.registers[step.rs1 as usize];
+                    let rs2_raw = step.registers[step.rs2 as usize];
+                    let rd_raw = step.registers[step.rd as usize];
+                    cols.raw_rs1 = F::from_canonical_u32(rs1_raw);
+                    cols.raw_rs2 = F::from_canonical_u32(rs2_raw);
+                    cols.raw_rd = F::from_canonical_u32(rd_raw);
+
+                    for b in 0..FP_OPERAND_BITS {
+                        cols.rs1_bits[b] = F::from_canonical_u32((rs1_raw >> b) & 1);
+                        cols.rs2_bits[b] = F::from_canonical_u32((rs2_raw >> b) & 1);
+                    }
+                    cols.sign_a = cols.rs1_bits[FP_OPERAND_BITS - 1];
+                    cols.sign_b = cols.rs2_bits[FP_OPERAND_BITS - 1];
+
+                    match group {
+                        FpGroup::Add => cols.is_add_op = F::ONE,
+                        FpGroup::Mul => {
+                            cols.is_mul_op = F::ONE;
+                            let mantissa_a = mantissa_with_implicit_bit(rs1_raw);
+                            let mantissa_b = mantissa_with_implicit_bit(rs2_raw);
+                            cols.mantissa_a = F::from_canonical_u32(mantissa_a);
+                            cols.mantissa_b = F::from_canonical_u32(mantissa_b);
+                            cols.mantissa_product =
+                                F::from_wrapped_u64(mantissa_a as u64 * mantissa_b as u64);
+                            cols.sign_result = if (rs1_raw >> 31) != (rs2_raw >> 31) {
+                                F::ONE
+                            } else {
+                                F::ZERO
+                            };
+                        }
+                        FpGroup::Cmp(variant) => {
+                            cols.is_cmp_op = F::ONE;
+                            let key_a = total_order_key(rs1_raw);
+                            let key_b = total_order_key(rs2_raw);
+                            let borrow_ab = key_a < key_b;
+                            let diff_ab: u64 = if borrow_ab {
+                                key_a as u64 + (1u64 << 32) - key_b as u64
+                            } else {
+                                (key_a - key_b) as u64
+                            };
+                            let borrow_ba = key_b < key_a;
+                            let diff_ba: u64 = if borrow_ba {
+                                key_b as u64 + (1u64 << 32) - key_a as u64
+                            } else {
+                                (key_b - key_a) as u64
+                            };
+                            cols.diff_ab = F::from_wrapped_u64(diff_ab);
+                            cols.borrow_ab = if borrow_ab { F::ONE } else { F::ZERO };
+                            cols.diff_ba = F::from_wrapped_u64(diff_ba);
+                            cols.borrow_ba = if borrow_ba { F::ONE } else { F::ZERO };
+                            match variant {
+                                CmpVariant::Feq => cols.is_feq = F::ONE,
+                                CmpVariant::Flt => cols.is_flt = F::ONE,
+                                CmpVariant::Fle => cols.is_fle = F::ONE,
+                            }
+                        }
+                        FpGroup::Other => {}
+                    }
+                }
+            }
+
+            let bus_values = [cols.funct, cols.raw_rs1, cols.raw_rs2, cols.raw_rd, cols.nonce];
+            let f = ext_add(&alpha, &fingerprint_n(&beta, &bus_values));
+            let f_inv = ext_inverse(f);
+            let neg_is_real = F::ZERO - cols.is_real;
+            for j in 0..4 {
+                phi[j] = phi[j] + neg_is_real * f_inv[j];
+            }
+            cols.f_inv = f_inv;
+            cols.phi = phi;
+        }
+
+        RowMajorMatrix::new(values, FpuColumns::<F>::NUM_COLUMNS)
+    }
+}
+
+/// `rs1_bits`/`rs2_bits`'s 24-bit mantissa including the implicit leading 1
+/// (0 for subnormals/zero) -- matches `FpuColumns::mantissa_a`'s doc
+/// comment.
+fn mantissa_with_implicit_bit(raw: u32) -> u32 {
+    let exp = (raw >> 23) & 0xFF;
+    let frac = raw & 0x7F_FFFF;
+    if exp == 0 {
+        frac
+    } else {
+        frac | (1 << 23)
+    }
+}
+
+/// The standard IEEE-754-as-unsigned-integer total-order key: flip the sign
+/// bit for non-negative numbers (so they sort above negative ones), or
+/// invert every bit for negative numbers (so more-negative values, which
+/// have a larger raw magnitude, sort below less-negative ones). Matches the
+/// `key` closure in `eval` bit-for-bit.
+fn total_order_key(raw: u32) -> u32 {
+    if raw >> 31 == 0 {
+        raw | 0x8000_0000
+    } else {
+        !raw
+    }
+}
+
+/// Which coarse RV32F operation group `(opcode, funct)` decodes to.
+enum FpGroup {
+    Add,
+    Mul,
+    Cmp(CmpVariant),
+    /// Recognized as a real FP row (FMA/convert/sign-injection/min-max/
+    /// classify/move) but not constrained by this chip yet -- still counted
+    /// in `is_real` so the bus multiplicity matches the CPU's, per the
+    /// module doc comment.
+    Other,
+}
+
+enum CmpVariant {
+    Feq,
+    Flt,
+    Fle,
+}
+
+/// RV32F major opcodes, matching `chips::cpu::trace::opcodes`' values --
+/// duplicated rather than imported, since `cpu::trace` is a private module
+/// (see `chips::alu`'s module doc comment for why this chip does the same
+/// for `decode_alu_op`).
+const OP_FP: u8 = 0b1010011;
+const OP_FMADD: u8 = 0b1000011;
+const OP_FMSUB: u8 = 0b1000111;
+const OP_FNMSUB: u8 = 0b1001011;
+const OP_FNMADD: u8 = 0b1001111;
+
+/// `funct`'s group code (bits 3..6, this chip's stand-in for RV32F's real
+/// `funct7`, packed down to the bits that distinguish the five groups this
+/// chip cares about) -- the same "pack only the bits that matter" packing
+/// convention `chips::cpu::trace::decode_shift_funct`/`decode_alu_funct`
+/// use for their own opcodes, applied to `OP_FP`'s wider operation set.
+const FP_GROUP_ADD: u8 = 0;
+const FP_GROUP_MUL: u8 = 1;
+const FP_GROUP_CMP: u8 = 2;
+const FP_GROUP_CONVERT: u8 = 3;
+
+/// Compare variant, packed into `funct`'s low 3 bits when the group code is
+/// `FP_GROUP_CMP` -- matches real RV32F's FEQ.S/FLT.S/FLE.S `funct3` values.
+const CMP_FUNCT3_FLE: u8 = 0b000;
+const CMP_FUNCT3_FLT: u8 = 0b001;
+const CMP_FUNCT3_FEQ: u8 = 0b010;
+
+/// Independently decide whether `(opcode, funct)` is a real RV32F row and,
+/// if so, which group it belongs to -- this chip's own re-derivation of
+/// `chips::cpu::trace`'s `OP_FP`/FMA-family dispatch, not a call to it (see
+/// the module doc comment).
+fn decode_fp_op(opcode: u8, funct: u8) -> Option<FpGroup> {
+    match opcode {
+        OP_FMADD | OP_FMSUB | OP_FNMSUB | OP_FNMADD => Some(FpGroup::Other),
+        OP_FP => {
+            let group = (funct >> 3) & 0b1111;
+            Some(match group {
+                FP_GROUP_ADD => FpGroup::Add,
+                FP_GROUP_MUL => FpGroup::Mul,
+                FP_GROUP_CMP => FpGroup::Cmp(match funct & 0b111 {
+                    CMP_FUNCT3_FEQ => CmpVariant::Feq,
+                    CMP_FUNCT3_FLT => CmpVariant::Flt,
+                    CMP_FUNCT3_FLE => CmpVariant::Fle,
+                    _ => return Some(FpGroup::Other),
+                }),
+                FP_GROUP_CONVERT => FpGroup::Other,
+                _ => FpGroup::Other,
+            })
+        }
+        _ => None,
+    }
+}
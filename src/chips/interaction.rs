@@ -0,0 +1,125 @@
+//! Cross-chip interaction bus
+//!
+//! Several chips need another chip to constrain work they can't check
+//! themselves (e.g. the CPU chip offloading ALU and memory operations).
+//! A chip that produces a tuple of values *sends* it onto a named bus; the
+//! chip responsible for constraining that tuple *receives* it. Soundness is
+//! established by a LogUp argument: summing `multiplicity / (challenge +
+//! fingerprint(values))` over every send and every receive (receives counted
+//! negatively) must equal zero across the whole proof. This module only
+//! defines the shared vocabulary (`Bus`, `Interaction`, the `send`/`receive`
+//! builders); the running-sum accumulator columns that actually enforce the
+//! argument are wired up per chip.
+
+use p3_air::AirBuilder;
+use p3_field::PrimeField32;
+
+use crate::chips::ext::ext_to_coords;
+use crate::chips::transcript::Transcript;
+
+/// Fiat-Shamir challenges shared by every chip that enforces a LogUp bus
+/// argument in its own `Air::eval` (see `machine::ZkIrMachine`, which is
+/// responsible for checking the argument closes across chips). Given as the
+/// base-field coordinates of a `crate::EF` element (see `chips::ext`): Baby
+/// Bear alone is only ~31 bits, far too small for this argument to be sound
+/// over a single base-field challenge.
+///
+/// Drawn from `chips::transcript::Transcript` rather than hand-picked, so
+/// they're the output of a real sponge over an explicit domain separator
+/// instead of arbitrary hex a reader could simply read off.
+///
+/// TODO: the transcript has nothing to absorb yet besides that domain
+/// separator -- there's no commitment scheme in this prover for it to bind
+/// to (see `chips::transcript`'s own TODO) -- so these are still fixed
+/// across runs, the same gap `MemoryChip::new` documents for its own
+/// (unrelated) grand-product challenges. Every chip on a given bus must
+/// agree on the same values, which is why they're computed once here rather
+/// than per chip.
+pub fn bus_challenges() -> ([u32; 4], [u32; 4]) {
+    let mut t = Transcript::new(b"zkir-prover/interaction-bus/v1");
+    let alpha = ext_to_coords(t.draw_ext_challenge()).map(|f| f.as_canonical_u32());
+    let beta = ext_to_coords(t.draw_ext_challenge()).map(|f| f.as_canonical_u32());
+    (alpha, beta)
+}
+
+/// Identifies which interaction bus a tuple is exchanged on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bus {
+    /// CPU -> ALU chip: `(opcode, alu_op, rs1_val, rs2_val, rd_val, nonce)`,
+    /// asserting `rd_val == alu_op(rs1_val, rs2_val)` (see
+    /// `chips::alu::AluChip`). `alu_op` disambiguates which operation, since
+    /// `opcode` alone doesn't distinguish e.g. ADD from AND: both share
+    /// `OP_ALU`/`OP_ALU_IMM`.
+    Alu,
+    /// CPU <-> Memory chip: `(mem_addr, mem_val, mem_is_write, cycle, nonce)`.
+    Memory,
+    /// CPU -> FPU chip: `(funct, rs1_val, rs2_val, rd_val, nonce)`.
+    Fpu,
+    /// Any chip -> range-check chip: `(value,)`, asserting `value` fits in
+    /// 16 bits. A 32-bit quantity is checked by decomposing it into two
+    /// limbs (see `chips::range::decompose_u32`) and sending each one
+    /// separately.
+    RangeCheck16,
+    /// CPU -> shift-power chip: `(shift_amount, pow)`, asserting `pow ==
+    /// 1 << shift_amount` for `shift_amount in 0..32` (see
+    /// `chips::shift::ShiftPowChip`).
+    ShiftPow,
+    /// CPU -> program chip: `(pc, opcode, rs1, rs2, rd, imm, funct)`,
+    /// asserting this row's fetch-decode matches a real committed program
+    /// entry at `pc` (see `chips::program::ProgramChip`).
+    Program,
+    /// CPU -> register chip: `(reg_index, value, is_write, seq, nonce)`,
+    /// one entry per register touch (see `chips::register::RegisterChip`).
+    /// Declared the same way `Bus::Alu` once was, before that one grew a real
+    /// closure: the real CPU<->register closure is the hand-rolled
+    /// `reg_bus_phi` argument in `chips::cpu::air::CpuChip::eval` and
+    /// `machine::ZkIrMachine`, not this generic bus (nothing receives it
+    /// yet).
+    Register,
+}
+
+/// Direction of a single interaction bus entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InteractionKind {
+    /// This row produced the tuple and is offering it to the bus.
+    Send,
+    /// This row is claiming responsibility for constraining the tuple.
+    Receive,
+}
+
+/// One entry in an interaction bus: a tuple of values, the bus it lives on,
+/// and how many times this row contributes it (usually a 0/1 selector).
+#[derive(Clone, Debug)]
+pub struct Interaction<Expr> {
+    pub kind: InteractionKind,
+    pub bus: Bus,
+    pub values: Vec<Expr>,
+    pub multiplicity: Expr,
+}
+
+impl<Expr> Interaction<Expr> {
+    pub fn new(kind: InteractionKind, bus: Bus, values: Vec<Expr>, multiplicity: Expr) -> Self {
+        Self {
+            kind,
+            bus,
+            values,
+            multiplicity,
+        }
+    }
+}
+
+/// Extension trait giving AIR builders `send`/`receive` helpers so chips can
+/// describe their bus interactions directly inside `eval`.
+pub trait InteractionBuilder: AirBuilder {
+    /// This row sends `values` onto `bus` with `multiplicity` copies.
+    fn send(&self, bus: Bus, values: Vec<Self::Expr>, multiplicity: Self::Expr) -> Interaction<Self::Expr> {
+        Interaction::new(InteractionKind::Send, bus, values, multiplicity)
+    }
+
+    /// This row receives `values` from `bus` with `multiplicity` copies.
+    fn receive(&self, bus: Bus, values: Vec<Self::Expr>, multiplicity: Self::Expr) -> Interaction<Self::Expr> {
+        Interaction::new(InteractionKind::Receive, bus, values, multiplicity)
+    }
+}
+
+impl<AB: AirBuilder> InteractionBuilder for AB {}
@@ -3,41 +3,127 @@
 //! Enforces memory consistency using a sorted trace approach.
 //! Memory accesses are sorted by (address, cycle), and constraints ensure
 //! that reads return the most recently written value.
+//!
+//! Sorting alone only proves something about *an* ordering of the accesses;
+//! it doesn't prove that ordering is a rearrangement of what the CPU
+//! actually did. We close that gap with a permutation (grand-product)
+//! argument between the execution-order log and the address-sorted log:
+//! each access is fingerprinted into a single field element via verifier
+//! challenges, and the running product of `(alpha - fingerprint)` over both
+//! orderings must agree at the end. Baby Bear is only ~31 bits, far too
+//! small for this to be sound with a single base-field challenge (a prover
+//! could find a collision by brute force), so the fingerprint, challenges,
+//! and accumulator all live in the degree-4 extension `GF(p^4)` with
+//! irreducible polynomial `x^4 - 11`; only the final boundary check reduces
+//! anything to the base field (an equality of extension elements, which is
+//! four base-field equalities).
 
 use std::borrow::{Borrow, BorrowMut};
 use std::ops::Deref;
 
 use p3_air::{Air, AirBuilder, BaseAir};
-use p3_field::{Field, FieldAlgebra};
+use p3_field::{Field, FieldAlgebra, PrimeField32};
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::Matrix;
 
+use crate::chips::ext::{
+    ext_add, ext_from_base, ext_inverse, ext_mul, ext_one, ext_sub, ext_to_generic, fingerprint,
+};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::chips::range::{self, RANGE_CHECK_BITS};
 use crate::trace::ExecutionTrace;
 
 /// Memory trace columns
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
 pub struct MemoryColumns<T> {
-    /// Memory address
+    /// Memory address (sorted ordering)
     pub address: T,
-    /// Cycle when access occurred
+    /// Cycle when access occurred (sorted ordering)
     pub cycle: T,
-    /// Value read or written
+    /// Value read or written (sorted ordering)
     pub value: T,
-    /// 1 if write, 0 if read
+    /// 1 if write, 0 if read (sorted ordering)
     pub is_write: T,
 
     // Helper columns for constraints
-    /// 1 if this row has the same address as the next row
+    /// 1 if this row has the same address as the next row. Bound both ways
+    /// by `eval`: when 1, `next.address == address` is asserted directly;
+    /// when 0, `next.address - address - 1` must recompose from
+    /// `addr_diff_lo/hi`, which (since `chips::range::RangeCheckChip`'s bus
+    /// is closed -- see `machine::check_range_bus_closure`) only has a valid
+    /// decomposition for an actual positive 32-bit gap, so a prover can't
+    /// claim 0 here for two rows that really share an address.
     pub same_addr_as_next: T,
-    /// Inverse of (next_addr - addr) when addresses differ, used for range check
-    pub addr_diff_inv: T,
-    /// Inverse of (next_cycle - cycle) when same address
-    pub cycle_diff_inv: T,
+    /// Low 16 bits of `next.address - address - 1`, witnessed when
+    /// `same_addr_as_next == 0` (meaningless, left zero, otherwise); see
+    /// `chips::range`.
+    pub addr_diff_lo: T,
+    /// High 16 bits of `next.address - address - 1`.
+    pub addr_diff_hi: T,
+    /// Low 16 bits of `next.cycle - cycle - 1`, witnessed when
+    /// `same_addr_as_next == 1` (meaningless, left zero, otherwise).
+    pub cycle_diff_lo: T,
+    /// High 16 bits of `next.cycle - cycle - 1`.
+    pub cycle_diff_hi: T,
+
+    // === Execution-order side of the permutation argument ===
+    /// Address of the access at this row index in execution order
+    pub exec_address: T,
+    /// Cycle of the access at this row index in execution order
+    pub exec_cycle: T,
+    /// Value of the access at this row index in execution order
+    pub exec_value: T,
+    /// Is-write flag of the access at this row index in execution order
+    pub exec_is_write: T,
+
+    // === Grand-product accumulators, degree-4 extension field ===
+    /// Running product of `(alpha - f)` over the sorted ordering, up to and
+    /// including this row.
+    pub acc_sorted: [T; 4],
+    /// Running product of `(alpha - f)` over the execution-order ordering,
+    /// up to and including this row.
+    pub acc_exec: [T; 4],
+
+    // === Cross-chip memory LogUp bus (receive side, see `machine`) ===
+    /// 1 if this row is a real (non-padding) access and so participates in
+    /// the bus with the CPU chip; 0 for trace padding beyond the real
+    /// accesses.
+    pub is_real: T,
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// over the execution-order columns (`exec_*`), matching the tuple the
+    /// CPU chip sent for the same access.
+    pub mem_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the receive side of the bus
+    /// (multiplicity `-1` per real access). `machine::ZkIrMachine` checks
+    /// this sums to zero against the CPU chip's send side.
+    pub mem_bus_phi: [T; 4],
+
+    // === Cross-chip range-check LogUp bus (send side, see `sends` and
+    // `machine::check_range_bus_closure`) ===
+    // Closes the gap `chips::range::RangeCheckChip`'s own doc comment used
+    // to flag: without this, `addr_diff_lo/hi`/`cycle_diff_lo/hi` were only
+    // checked for *recomposition* (`lo + 2^16*hi == diff`) against an inert
+    // table, so a prover could pick any `lo`/`hi` pair and the ordering
+    // constraints above would accept an underflowed (wraparound) "gap" --
+    // i.e. address/cycle strict-increase was unenforced. One fingerprint
+    // inverse per limb sent this row (zero multiplicity on whichever gap
+    // didn't fire doesn't need a meaningful inverse, but the value is 0 and
+    // `alpha + 0` is still invertible, so the witness is real either way).
+    pub range_addr_lo_f_inv: [T; 4],
+    pub range_addr_hi_f_inv: [T; 4],
+    pub range_cycle_lo_f_inv: [T; 4],
+    pub range_cycle_hi_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this row's four range-check sends --
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `RangeCheckColumns::phi`.
+    pub range_bus_phi: [T; 4],
 }
 
 /// Number of columns in the memory trace
-pub const MEMORY_NUM_COLUMNS: usize = 7;
+pub const MEMORY_NUM_COLUMNS: usize = 9 + 4 + 4 + 4 + 1 + 4 + 4 + 4 * 4 + 4;
 
 impl<T> MemoryColumns<T> {
     pub const NUM_COLUMNS: usize = MEMORY_NUM_COLUMNS;
@@ -56,7 +142,42 @@ impl<T> BorrowMut<MemoryColumns<T>> for [T; MEMORY_NUM_COLUMNS] {
 }
 
 /// Memory Chip enforcing read/write consistency
-pub struct MemoryChip;
+pub struct MemoryChip {
+    /// Fiat-Shamir challenge `alpha`, in `crate::EF` (see `chips::ext`).
+    ///
+    /// TODO: these should be drawn by the verifier after committing to the
+    /// execution-order and sorted traces (the prover module doesn't exist
+    /// yet to wire that up). Derived deterministically for now so the chip
+    /// is self-contained; swapping in real Fiat-Shamir challenges is purely
+    /// a matter of threading them in from the transcript.
+    pub alpha: crate::EF,
+    /// Fiat-Shamir challenge `beta`, in `crate::EF`.
+    pub beta: crate::EF,
+}
+
+impl Default for MemoryChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryChip {
+    pub fn new() -> Self {
+        let mut t = crate::chips::transcript::Transcript::new(b"zkir-prover/memory-chip/v1");
+        Self {
+            alpha: t.draw_ext_challenge(),
+            beta: t.draw_ext_challenge(),
+        }
+    }
+
+    fn alpha_ext<T: FieldAlgebra>(&self) -> [T; 4] {
+        ext_to_generic(self.alpha)
+    }
+
+    fn beta_ext<T: FieldAlgebra>(&self) -> [T; 4] {
+        ext_to_generic(self.beta)
+    }
+}
 
 impl<F: Field> BaseAir<F> for MemoryChip {
     fn width(&self) -> usize {
@@ -88,17 +209,42 @@ impl<AB: AirBuilder> Air<AB> for MemoryChip {
             .when(local.same_addr_as_next)
             .assert_eq(next.address, local.address);
 
-        // When same_addr_as_next = 0: next.address > local.address
-        // (enforced via range check on next.address - local.address - 1)
+        // When same_addr_as_next = 0: next.address > local.address, proven
+        // by range-checking `next.address - local.address - 1` as a valid
+        // (non-negative) 32-bit value through `chips::range`: a value with
+        // an underflowed (huge) bit pattern can't be decomposed into two
+        // 16-bit limbs that recompose to it.
+        //
+        // Gated by `next.is_real`: `generate_trace` leaves `same_addr_as_next`
+        // and both diff columns at zero on the last real row (there's no
+        // `sorted.get(i+1)` to diff against), so without this gate the
+        // transition into the all-zero padding row would assert
+        // `0 - address - 1 == 0`, which is false for any real trace whose
+        // access count isn't already a power of two.
+        let two_pow_16 = AB::Expr::from_canonical_u32(1 << RANGE_CHECK_BITS);
+        let addr_diff_composed: AB::Expr =
+            local.addr_diff_lo.into() + two_pow_16.clone() * local.addr_diff_hi.into();
+        builder
+            .when_transition()
+            .when(next.is_real.into())
+            .when(AB::Expr::ONE - local.same_addr_as_next.into())
+            .assert_eq(
+                next.address.into() - local.address.into() - AB::Expr::ONE,
+                addr_diff_composed,
+            );
 
-        // Cycle ordering within same address: cycles must be strictly increasing
-        // Enforced via range check on (next.cycle - local.cycle - 1)
+        // Cycle ordering within same address: cycles must be strictly
+        // increasing, proven the same way via `cycle_diff_lo`/`_hi`, and
+        // gated by `next.is_real` for the same padding-boundary reason.
+        let cycle_diff_composed: AB::Expr =
+            local.cycle_diff_lo.into() + two_pow_16 * local.cycle_diff_hi.into();
         builder
             .when_transition()
+            .when(next.is_real.into())
             .when(local.same_addr_as_next)
-            .assert_zero(
-                (next.cycle.into() - local.cycle.into() - AB::Expr::ONE) * local.cycle_diff_inv.into()
-                    - AB::Expr::ONE,
+            .assert_eq(
+                next.cycle.into() - local.cycle.into() - AB::Expr::ONE,
+                cycle_diff_composed,
             );
 
         // Read consistency: reads return last written value
@@ -111,40 +257,394 @@ impl<AB: AirBuilder> Air<AB> for MemoryChip {
             .when(next_is_read)
             .assert_eq(next.value, local.value);
 
-        // First access to an address must be a write (or value must be zero)
-        // TODO: implement constraint for initial memory state
+        // First access to an address must be a write (or value must be zero):
+        // uninitialized memory reads as zero, so a read can't be the first
+        // touch of an address unless it's reading that default. Row 0 is
+        // trivially the first access to whatever address it holds; every
+        // other row is the first access to its address exactly when the
+        // previous row's `same_addr_as_next` was 0 -- from this row's own
+        // transition, that's "`next` is first-for-its-address whenever
+        // `local.same_addr_as_next == 0`".
+        builder
+            .when_first_row()
+            .assert_zero((AB::Expr::ONE - local.is_write.into()) * local.value.into());
+
+        builder.when_transition().when(next.is_real.into()).assert_zero(
+            (AB::Expr::ONE - local.same_addr_as_next.into())
+                * (AB::Expr::ONE - next.is_write.into())
+                * next.value.into(),
+        );
+
+        // === Permutation argument between execution order and sorted order ===
+        //
+        // Both accumulators start (at row 0) as `alpha - f(row 0)` and are
+        // extended by one more factor per row; the two orderings contain
+        // the same multiset of accesses iff the final products agree.
+        let alpha = self.alpha_ext::<AB::Expr>();
+        let beta = self.beta_ext::<AB::Expr>();
+
+        let f_sorted_local = fingerprint(
+            &beta,
+            local.address.into(),
+            local.cycle.into(),
+            local.value.into(),
+            local.is_write.into(),
+        );
+        let f_exec_local = fingerprint(
+            &beta,
+            local.exec_address.into(),
+            local.exec_cycle.into(),
+            local.exec_value.into(),
+            local.exec_is_write.into(),
+        );
+        let term_sorted_local = ext_sub(&alpha, &f_sorted_local);
+        let term_exec_local = ext_sub(&alpha, &f_exec_local);
+
+        // Boundary: the first row's accumulator is just its own factor.
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.acc_sorted[i], term_sorted_local[i].clone());
+            builder
+                .when_first_row()
+                .assert_eq(local.acc_exec[i], term_exec_local[i].clone());
+        }
+
+        // Transition: each row's accumulator is the previous one times this
+        // row's factor.
+        let acc_sorted_local: [AB::Expr; 4] = local.acc_sorted.map(Into::into);
+        let acc_exec_local: [AB::Expr; 4] = local.acc_exec.map(Into::into);
+
+        let f_sorted_next = fingerprint(
+            &beta,
+            next.address.into(),
+            next.cycle.into(),
+            next.value.into(),
+            next.is_write.into(),
+        );
+        let f_exec_next = fingerprint(
+            &beta,
+            next.exec_address.into(),
+            next.exec_cycle.into(),
+            next.exec_value.into(),
+            next.exec_is_write.into(),
+        );
+        let term_sorted_next = ext_sub(&alpha, &f_sorted_next);
+        let term_exec_next = ext_sub(&alpha, &f_exec_next);
+
+        let next_acc_sorted = ext_mul(&acc_sorted_local, &term_sorted_next);
+        let next_acc_exec = ext_mul(&acc_exec_local, &term_exec_next);
+
+        for i in 0..4 {
+            builder
+                .when_transition()
+                .assert_eq(next.acc_sorted[i], next_acc_sorted[i].clone());
+            builder
+                .when_transition()
+                .assert_eq(next.acc_exec[i], next_acc_exec[i].clone());
+        }
+
+        // Boundary: on the last row, the two products must agree.
+        for i in 0..4 {
+            builder
+                .when_last_row()
+                .assert_eq(local.acc_sorted[i], local.acc_exec[i]);
+        }
+
+        // === Cross-chip memory LogUp bus ===
+        //
+        // The receive side of the bus the CPU chip sends on (see
+        // `chips::cpu::air::CpuChip::eval`): this chip's `mem_bus_phi`
+        // accumulates `-1 * 1/fingerprint` per real access over the
+        // execution-order columns, which `machine::ZkIrMachine` checks sums
+        // to zero against the CPU's `+1` accumulator. This is what finally
+        // ties the sorted trace above to what the CPU actually executed,
+        // making the address/cycle sort order meaningful rather than an
+        // arbitrary self-consistent rearrangement.
+        builder.assert_zero(local.is_real.into() * (AB::Expr::ONE - local.is_real.into()));
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let bus_beta: [AB::Expr; 4] = raw_bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let bus_f_local = fingerprint(
+            &bus_beta,
+            local.exec_address.into(),
+            local.exec_cycle.into(),
+            local.exec_value.into(),
+            local.exec_is_write.into(),
+        );
+        let bus_f_local = ext_add(&bus_alpha, &bus_f_local);
+
+        let bus_f_inv_local: [AB::Expr; 4] = local.mem_bus_f_inv.map(Into::into);
+        let check_local = ext_mul(&bus_f_local, &bus_f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let neg_is_real_local = AB::Expr::ZERO - local.is_real.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.mem_bus_phi[i], neg_is_real_local.clone() * bus_f_inv_local[i].clone());
+        }
+
+        let neg_is_real_next = AB::Expr::ZERO - next.is_real.into();
+        let bus_f_inv_next: [AB::Expr; 4] = next.mem_bus_f_inv.map(Into::into);
+        for i in 0..4 {
+            let term_next = neg_is_real_next.clone() * bus_f_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.mem_bus_phi[i].into() - local.mem_bus_phi[i].into(),
+                term_next,
+            );
+        }
+
+        // === Cross-chip range-check LogUp bus (send side) ===
+        //
+        // Same fingerprint/phi shape as the memory bus above, but against
+        // `Bus::RangeCheck16`'s single-value tuple (`alpha + value`, no
+        // `beta` needed) and four terms per row instead of one, one per
+        // limb in `sends`. `machine::check_range_bus_closure` checks this
+        // sums to zero against `RangeCheckColumns::phi`.
+        let is_addr_gap_local: AB::Expr = AB::Expr::ONE - local.same_addr_as_next.into();
+        let is_cycle_gap_local: AB::Expr = local.same_addr_as_next.into();
+
+        let range_f_addr_lo_local = ext_add(&bus_alpha, &ext_from_base(local.addr_diff_lo.into()));
+        let range_f_addr_hi_local = ext_add(&bus_alpha, &ext_from_base(local.addr_diff_hi.into()));
+        let range_f_cycle_lo_local = ext_add(&bus_alpha, &ext_from_base(local.cycle_diff_lo.into()));
+        let range_f_cycle_hi_local = ext_add(&bus_alpha, &ext_from_base(local.cycle_diff_hi.into()));
+
+        let range_f_inv_addr_lo_local: [AB::Expr; 4] = local.range_addr_lo_f_inv.map(Into::into);
+        let range_f_inv_addr_hi_local: [AB::Expr; 4] = local.range_addr_hi_f_inv.map(Into::into);
+        let range_f_inv_cycle_lo_local: [AB::Expr; 4] = local.range_cycle_lo_f_inv.map(Into::into);
+        let range_f_inv_cycle_hi_local: [AB::Expr; 4] = local.range_cycle_hi_f_inv.map(Into::into);
+
+        for (f, f_inv) in [
+            (&range_f_addr_lo_local, &range_f_inv_addr_lo_local),
+            (&range_f_addr_hi_local, &range_f_inv_addr_hi_local),
+            (&range_f_cycle_lo_local, &range_f_inv_cycle_lo_local),
+            (&range_f_cycle_hi_local, &range_f_inv_cycle_hi_local),
+        ] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+
+        let range_term_local: [AB::Expr; 4] = std::array::from_fn(|i| {
+            is_addr_gap_local.clone() * range_f_inv_addr_lo_local[i].clone()
+                + is_addr_gap_local.clone() * range_f_inv_addr_hi_local[i].clone()
+                + is_cycle_gap_local.clone() * range_f_inv_cycle_lo_local[i].clone()
+                + is_cycle_gap_local.clone() * range_f_inv_cycle_hi_local[i].clone()
+        });
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.range_bus_phi[i], range_term_local[i].clone());
+        }
+
+        let is_addr_gap_next: AB::Expr = AB::Expr::ONE - next.same_addr_as_next.into();
+        let is_cycle_gap_next: AB::Expr = next.same_addr_as_next.into();
+        let range_f_addr_lo_next = ext_add(&bus_alpha, &ext_from_base(next.addr_diff_lo.into()));
+        let range_f_addr_hi_next = ext_add(&bus_alpha, &ext_from_base(next.addr_diff_hi.into()));
+        let range_f_cycle_lo_next = ext_add(&bus_alpha, &ext_from_base(next.cycle_diff_lo.into()));
+        let range_f_cycle_hi_next = ext_add(&bus_alpha, &ext_from_base(next.cycle_diff_hi.into()));
+        let range_f_inv_addr_lo_next: [AB::Expr; 4] = next.range_addr_lo_f_inv.map(Into::into);
+        let range_f_inv_addr_hi_next: [AB::Expr; 4] = next.range_addr_hi_f_inv.map(Into::into);
+        let range_f_inv_cycle_lo_next: [AB::Expr; 4] = next.range_cycle_lo_f_inv.map(Into::into);
+        let range_f_inv_cycle_hi_next: [AB::Expr; 4] = next.range_cycle_hi_f_inv.map(Into::into);
+        for (f, f_inv) in [
+            (&range_f_addr_lo_next, &range_f_inv_addr_lo_next),
+            (&range_f_addr_hi_next, &range_f_inv_addr_hi_next),
+            (&range_f_cycle_lo_next, &range_f_inv_cycle_lo_next),
+            (&range_f_cycle_hi_next, &range_f_inv_cycle_hi_next),
+        ] {
+            let check = ext_mul(f, f_inv);
+            for i in 0..4 {
+                builder.when_transition().assert_eq(check[i].clone(), one[i].clone());
+            }
+        }
+        let range_term_next: [AB::Expr; 4] = std::array::from_fn(|i| {
+            is_addr_gap_next.clone() * range_f_inv_addr_lo_next[i].clone()
+                + is_addr_gap_next.clone() * range_f_inv_addr_hi_next[i].clone()
+                + is_cycle_gap_next.clone() * range_f_inv_cycle_lo_next[i].clone()
+                + is_cycle_gap_next.clone() * range_f_inv_cycle_hi_next[i].clone()
+        });
+        for i in 0..4 {
+            builder.when_transition().assert_eq(
+                next.range_bus_phi[i].into() - local.range_bus_phi[i].into(),
+                range_term_next[i].clone(),
+            );
+        }
     }
 }
 
 impl MemoryChip {
-    /// Generate the memory trace sorted by (address, cycle)
-    pub fn generate_trace<F: Field>(&self, trace: &ExecutionTrace) -> RowMajorMatrix<F> {
+    /// The interaction bus tuples this row sends: the two 16-bit limbs of
+    /// whichever strictly-increasing gap (address or cycle) this row
+    /// actually witnesses, each range-checked through `Bus::RangeCheck16`
+    /// (see `chips::range`). The other gap's limbs are zero and sent with
+    /// multiplicity zero, since `RangeCheckColumns::value == 0` is already
+    /// in the table and a zero multiplicity doesn't perturb the lookup sum.
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &MemoryColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        let is_addr_gap: AB::Expr = AB::Expr::ONE - local.same_addr_as_next.into();
+        let is_cycle_gap: AB::Expr = local.same_addr_as_next.into();
+
+        vec![
+            builder.send(Bus::RangeCheck16, vec![local.addr_diff_lo.into()], is_addr_gap.clone()),
+            builder.send(Bus::RangeCheck16, vec![local.addr_diff_hi.into()], is_addr_gap),
+            builder.send(Bus::RangeCheck16, vec![local.cycle_diff_lo.into()], is_cycle_gap.clone()),
+            builder.send(Bus::RangeCheck16, vec![local.cycle_diff_hi.into()], is_cycle_gap),
+        ]
+    }
+
+    /// Generate the memory trace. Row `i` holds both the `i`-th access in
+    /// address-sorted order and the `i`-th access in execution order, plus
+    /// the running grand-product accumulators for each.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `chips::cpu::trace::generate_cpu_trace`: the cross-chip bus columns
+    /// below go through `crate::EF`.
+    pub fn generate_trace(
+        &self,
+        trace: &ExecutionTrace,
+    ) -> (RowMajorMatrix<crate::F>, [u64; range::RANGE_CHECK_SIZE]) {
+        type F = crate::F;
+
         let sorted = trace.sorted_memory_log();
+        let exec = &trace.memory_log;
+        debug_assert_eq!(sorted.len(), exec.len());
+
         let num_accesses = sorted.len();
         let trace_len = num_accesses.next_power_of_two().max(2);
 
         let mut values = vec![F::ZERO; trace_len * MemoryColumns::<F>::NUM_COLUMNS];
 
-        for (i, access) in sorted.iter().enumerate() {
+        let alpha = self.alpha_ext::<F>();
+        let beta = self.beta_ext::<F>();
+        let mut acc_sorted = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        let mut acc_exec = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let bus_beta = raw_bus_beta.map(F::from_canonical_u32);
+        let mut mem_bus_phi = [F::ZERO; 4];
+        let mut range_bus_phi = [F::ZERO; 4];
+        let mut range_multiplicities = [0u64; range::RANGE_CHECK_SIZE];
+
+        for i in 0..trace_len {
             let row_offset = i * MemoryColumns::<F>::NUM_COLUMNS;
-            let row: &mut [F; MEMORY_NUM_COLUMNS] = (&mut values[row_offset..row_offset + MemoryColumns::<F>::NUM_COLUMNS]).try_into().unwrap();
+            let row: &mut [F; MEMORY_NUM_COLUMNS] =
+                (&mut values[row_offset..row_offset + MemoryColumns::<F>::NUM_COLUMNS])
+                    .try_into()
+                    .unwrap();
             let cols: &mut MemoryColumns<F> = row.borrow_mut();
 
-            cols.address = F::from_canonical_u32(access.address);
-            cols.cycle = F::from_canonical_u64(access.cycle);
-            cols.value = F::from_canonical_u32(access.value);
-            cols.is_write = if access.is_write { F::ONE } else { F::ZERO };
-
-            // Check if next row has same address
-            if i + 1 < sorted.len() {
-                cols.same_addr_as_next = if sorted[i + 1].address == access.address {
-                    F::ONE
-                } else {
-                    F::ZERO
-                };
+            // Padding rows beyond the real accesses repeat an all-zero
+            // access on both sides, which keeps the two products in lock
+            // step without affecting soundness (they contribute the same
+            // factor to both accumulators).
+            if let Some(access) = sorted.get(i) {
+                cols.address = F::from_canonical_u32(access.address);
+                cols.cycle = F::from_canonical_u64(access.cycle);
+                cols.value = F::from_canonical_u32(access.value);
+                cols.is_write = if access.is_write { F::ONE } else { F::ZERO };
+
+                if let Some(next_access) = sorted.get(i + 1) {
+                    if next_access.address == access.address {
+                        cols.same_addr_as_next = F::ONE;
+                        let diff = (next_access.cycle - access.cycle - 1) as u32;
+                        let (lo, hi) = range::decompose_u32(diff);
+                        cols.cycle_diff_lo = F::from_canonical_u32(lo);
+                        cols.cycle_diff_hi = F::from_canonical_u32(hi);
+                    } else {
+                        let diff = next_access.address - access.address - 1;
+                        let (lo, hi) = range::decompose_u32(diff);
+                        cols.addr_diff_lo = F::from_canonical_u32(lo);
+                        cols.addr_diff_hi = F::from_canonical_u32(hi);
+                    }
+                }
+            }
+
+            if let Some(access) = exec.get(i) {
+                cols.exec_address = F::from_canonical_u32(access.address);
+                cols.exec_cycle = F::from_canonical_u64(access.cycle);
+                cols.exec_value = F::from_canonical_u32(access.value);
+                cols.exec_is_write = if access.is_write { F::ONE } else { F::ZERO };
+                cols.is_real = F::ONE;
+            }
+
+            let bus_f = fingerprint(
+                &bus_beta,
+                cols.exec_address,
+                cols.exec_cycle,
+                cols.exec_value,
+                cols.exec_is_write,
+            );
+            let bus_f = ext_add(&bus_alpha, &bus_f);
+            let bus_f_inv = ext_inverse(bus_f);
+            let neg_is_real = F::ZERO - cols.is_real;
+            for j in 0..4 {
+                mem_bus_phi[j] = mem_bus_phi[j] + neg_is_real * bus_f_inv[j];
+            }
+            cols.mem_bus_f_inv = bus_f_inv;
+            cols.mem_bus_phi = mem_bus_phi;
+
+            // Cross-chip range-check bus (send side): same four limbs and
+            // multiplicities `sends` declares, fingerprinted against
+            // `bus_alpha` alone (`Bus::RangeCheck16`'s tuple is a single
+            // value, so there's no `beta` term to mix in).
+            let is_addr_gap = F::ONE - cols.same_addr_as_next;
+            let is_cycle_gap = cols.same_addr_as_next;
+            let limbs = [
+                (cols.addr_diff_lo, is_addr_gap),
+                (cols.addr_diff_hi, is_addr_gap),
+                (cols.cycle_diff_lo, is_cycle_gap),
+                (cols.cycle_diff_hi, is_cycle_gap),
+            ];
+            let mut f_invs = [[F::ZERO; 4]; 4];
+            for (slot, (value, multiplicity)) in limbs.iter().enumerate() {
+                let f = ext_add(&bus_alpha, &ext_from_base(*value));
+                let f_inv = ext_inverse(f);
+                f_invs[slot] = f_inv;
+                for j in 0..4 {
+                    range_bus_phi[j] = range_bus_phi[j] + *multiplicity * f_inv[j];
+                }
+                if multiplicity.as_canonical_u32() == 1 {
+                    range_multiplicities[value.as_canonical_u32() as usize] += 1;
+                }
             }
+            cols.range_addr_lo_f_inv = f_invs[0];
+            cols.range_addr_hi_f_inv = f_invs[1];
+            cols.range_cycle_lo_f_inv = f_invs[2];
+            cols.range_cycle_hi_f_inv = f_invs[3];
+            cols.range_bus_phi = range_bus_phi;
+
+            let f_sorted = fingerprint(&beta, cols.address, cols.cycle, cols.value, cols.is_write);
+            let f_exec = fingerprint(
+                &beta,
+                cols.exec_address,
+                cols.exec_cycle,
+                cols.exec_value,
+                cols.exec_is_write,
+            );
+            acc_sorted = ext_mul(&acc_sorted, &ext_sub(&alpha, &f_sorted));
+            acc_exec = ext_mul(&acc_exec, &ext_sub(&alpha, &f_exec));
+
+            cols.acc_sorted = acc_sorted;
+            cols.acc_exec = acc_exec;
         }
 
-        RowMajorMatrix::new(values, MemoryColumns::<F>::NUM_COLUMNS)
+        debug_assert_eq!(acc_sorted, acc_exec);
+
+        (
+            RowMajorMatrix::new(values, MemoryColumns::<F>::NUM_COLUMNS),
+            range_multiplicities,
+        )
     }
 }
@@ -4,14 +4,33 @@
 //! - CPU Chip: Main execution with ~32 trace columns
 //! - Memory Chip: Memory consistency via sorted trace
 //! - Range Check Chip: 32-bit value validation
+//! - FPU Chip: RV32F floating-point arithmetic
 //! - Syscall Chips: Dedicated chips for crypto operations
+//!
+//! Chips that can't constrain everything about a value themselves (e.g. the
+//! CPU chip's ALU, memory, and floating-point operations) hand it off to
+//! another chip over the `interaction` bus.
 
+pub mod alu;
 pub mod cpu;
+pub mod ext;
+pub mod fpu;
+pub mod interaction;
 pub mod memory;
+pub mod program;
 pub mod range;
+pub mod register;
+pub mod shift;
 pub mod syscall;
+pub mod transcript;
 
+pub use alu::AluChip;
 pub use cpu::CpuChip;
+pub use fpu::FpuChip;
+pub use interaction::{Bus, Interaction, InteractionBuilder, InteractionKind};
 pub use memory::MemoryChip;
+pub use program::ProgramChip;
 pub use range::RangeCheckChip;
-pub use syscall::{Poseidon2Chip, Sha256Chip};
+pub use register::RegisterChip;
+pub use shift::ShiftPowChip;
+pub use syscall::{Bn254ScalarChip, KeccakChip, MemCopyChip, Poseidon2Chip, Sha256Chip};
@@ -0,0 +1,265 @@
+//! Program (bytecode) chip: commits the static program as a lookup table
+//! keyed by `pc`, so the CPU chip's decoded fields on every row can be tied
+//! back to a real instruction instead of one a malicious prover invented.
+//!
+//! This is the fetch-decode half of the uniform per-step design: the CPU
+//! chip's `eval` only ever constrains what a row *does* with its decoded
+//! `opcode`/`rs1`/`rs2`/`rd`/`imm`/`funct`, never where they came from. This
+//! chip is the other half -- every row the CPU sends onto `Bus::Program`
+//! must match one of this table's rows exactly, enforced by the `f_inv`/
+//! `phi` LogUp columns below against `CpuColumns::program_bus_phi` (see
+//! `machine::ZkIrMachine::check_program_bus_closure`); `receives` below
+//! builds the same tuple through the generic interaction-bus vocabulary too,
+//! but (like every other chip's `receives`/`sends`) nothing aggregates that
+//! return value -- the real argument is the hand-rolled accumulator.
+//!
+//! TODO: closing this against the program hash exposed in `PublicInputs` --
+//! i.e. checking this table's commitment equals `program_hash` -- needs the
+//! `proof`/`prover` modules `lib.rs` already declares but don't exist yet in
+//! this tree; that binding has nowhere to live until they do.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_inverse, ext_mul, ext_one, fingerprint_n};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::trace::ProgramEntry;
+
+/// Program trace columns: one row per committed `ProgramEntry`, in `pc`
+/// order.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProgramColumns<T> {
+    /// Program counter for this instruction.
+    pub pc: T,
+    /// Opcode
+    pub opcode: T,
+    /// Source register 1
+    pub rs1: T,
+    /// Source register 2
+    pub rs2: T,
+    /// Destination register
+    pub rd: T,
+    /// Immediate value
+    pub imm: T,
+    /// Function code (funct3 + funct7 combined, see `CpuColumns::funct`)
+    pub funct: T,
+    /// Number of times the CPU chip fetched this row this proof.
+    pub multiplicity: T,
+
+    // === Cross-chip program LogUp bus (receive side, see
+    // `machine::check_program_bus_closure`) ===
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// `alpha + fingerprint_n(beta, (pc, opcode, rs1, rs2, rd, imm, funct))`.
+    pub f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `-multiplicity /
+    /// fingerprint` over this table -- the receive side of `Bus::Program`.
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `CpuColumns::program_bus_phi`.
+    pub phi: [T; 4],
+}
+
+/// Number of columns in the program trace.
+pub const PROGRAM_NUM_COLUMNS: usize = 8 + 4 + 4;
+
+impl<T> ProgramColumns<T> {
+    pub const NUM_COLUMNS: usize = PROGRAM_NUM_COLUMNS;
+}
+
+impl<T> Borrow<ProgramColumns<T>> for [T; PROGRAM_NUM_COLUMNS] {
+    fn borrow(&self) -> &ProgramColumns<T> {
+        unsafe { &*(self.as_ptr() as *const ProgramColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<ProgramColumns<T>> for [T; PROGRAM_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut ProgramColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut ProgramColumns<T>) }
+    }
+}
+
+/// Program chip: a fixed table of the committed bytecode, one row per
+/// instruction in `pc` order.
+pub struct ProgramChip;
+
+impl Default for ProgramChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgramChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for ProgramChip {
+    fn width(&self) -> usize {
+        ProgramColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for ProgramChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; PROGRAM_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; PROGRAM_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let local: &ProgramColumns<AB::Var> = local_arr.borrow();
+        let next: &ProgramColumns<AB::Var> = next_arr.borrow();
+
+        // Instructions are laid out one word (4 bytes) apart, in order --
+        // the same boundary + transition shape `chips::range::RangeCheckChip`
+        // and `chips::shift::ShiftPowChip` use to pin their own tables down,
+        // here pinning down that this is really a contiguous program rather
+        // than an arbitrary bag of rows a prover could reorder or repeat.
+        builder.when_first_row().assert_zero(local.pc.into());
+        builder
+            .when_transition()
+            .assert_eq(next.pc, local.pc.into() + AB::Expr::from_canonical_u32(4));
+
+        // === Cross-chip program LogUp bus (receive side) ===
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let bus_beta: [AB::Expr; 4] = raw_bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let values_local = [
+            local.pc.into(),
+            local.opcode.into(),
+            local.rs1.into(),
+            local.rs2.into(),
+            local.rd.into(),
+            local.imm.into(),
+            local.funct.into(),
+        ];
+        let f_local = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &values_local));
+        let f_inv_local: [AB::Expr; 4] = local.f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let neg_multiplicity_local = AB::Expr::ZERO - local.multiplicity.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.phi[i], neg_multiplicity_local.clone() * f_inv_local[i].clone());
+        }
+
+        let values_next = [
+            next.pc.into(),
+            next.opcode.into(),
+            next.rs1.into(),
+            next.rs2.into(),
+            next.rd.into(),
+            next.imm.into(),
+            next.funct.into(),
+        ];
+        let f_next = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &values_next));
+        let f_inv_next: [AB::Expr; 4] = next.f_inv.map(Into::into);
+        let check_next = ext_mul(&f_next, &f_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_next[i].clone(), one[i].clone());
+        }
+        let neg_multiplicity_next = AB::Expr::ZERO - next.multiplicity.into();
+        for i in 0..4 {
+            let term_next = neg_multiplicity_next.clone() * f_inv_next[i].clone();
+            builder
+                .when_transition()
+                .assert_eq(next.phi[i].into() - local.phi[i].into(), term_next);
+        }
+    }
+}
+
+impl ProgramChip {
+    /// The receive side of `Bus::Program`: this row's decoded instruction,
+    /// counted `multiplicity` times.
+    pub fn receives<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &ProgramColumns<AB::Var>,
+    ) -> Interaction<AB::Expr> {
+        builder.receive(
+            Bus::Program,
+            vec![
+                local.pc.into(),
+                local.opcode.into(),
+                local.rs1.into(),
+                local.rs2.into(),
+                local.rd.into(),
+                local.imm.into(),
+                local.funct.into(),
+            ],
+            local.multiplicity.into(),
+        )
+    }
+
+    /// Generate the program trace from the committed `ProgramEntry` list,
+    /// padded with trailing zero (`opcode = 0`) rows up to a power of two so
+    /// the `pc`-increments-by-4 transition constraint stays satisfiable.
+    /// `multiplicities[i]` is how many times the CPU chip fetched
+    /// `program[i]` this proof -- see `chips::cpu::trace::generate_cpu_trace`,
+    /// whose second return value this is, threaded through `machine`.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `MemoryChip::generate_trace`/`RangeCheckChip::generate_trace`: the bus
+    /// `phi` column goes through `crate::EF`.
+    pub fn generate_trace(&self, program: &[ProgramEntry], multiplicities: &[u64]) -> RowMajorMatrix<crate::F> {
+        type F = crate::F;
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let bus_beta = raw_bus_beta.map(F::from_canonical_u32);
+
+        let trace_len = program.len().next_power_of_two().max(2);
+        let mut values = vec![F::ZERO; trace_len * ProgramColumns::<F>::NUM_COLUMNS];
+        let mut phi = [F::ZERO; 4];
+
+        for i in 0..trace_len {
+            let row_offset = i * ProgramColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; PROGRAM_NUM_COLUMNS] = (&mut values
+                [row_offset..row_offset + ProgramColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+            let cols: &mut ProgramColumns<F> = row.borrow_mut();
+
+            if let Some(entry) = program.get(i) {
+                cols.pc = F::from_canonical_u32(entry.pc);
+                cols.opcode = F::from_canonical_u32(entry.opcode as u32);
+                cols.rs1 = F::from_canonical_u32(entry.rs1 as u32);
+                cols.rs2 = F::from_canonical_u32(entry.rs2 as u32);
+                cols.rd = F::from_canonical_u32(entry.rd as u32);
+                cols.imm = F::from_canonical_u32(entry.imm as u32);
+                cols.funct = F::from_canonical_u32(entry.funct as u32);
+                cols.multiplicity = F::from_canonical_u64(multiplicities.get(i).copied().unwrap_or(0));
+            } else {
+                // Padding rows keep the `pc = pc + 4` enumeration going
+                // rather than leaving them at `pc = 0` (which would collide
+                // with row 0 and break the transition constraint above); the
+                // CPU chip never fetches a pc outside the real program, so
+                // `multiplicity` stays zero.
+                cols.pc = F::from_canonical_u32((i as u32) * 4);
+            }
+
+            let values_tuple = [cols.pc, cols.opcode, cols.rs1, cols.rs2, cols.rd, cols.imm, cols.funct];
+            let f = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &values_tuple));
+            let f_inv = ext_inverse(f);
+            let neg_multiplicity = F::ZERO - cols.multiplicity;
+            for j in 0..4 {
+                phi[j] = phi[j] + neg_multiplicity * f_inv[j];
+            }
+            cols.f_inv = f_inv;
+            cols.phi = phi;
+        }
+
+        RowMajorMatrix::new(values, ProgramColumns::<F>::NUM_COLUMNS)
+    }
+}
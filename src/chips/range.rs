@@ -0,0 +1,211 @@
+//! Range-check chip: enumerates every 16-bit value, 0..2^16, so another
+//! chip can prove a witnessed value fits in 16 bits by looking it up
+//! through the interaction bus instead of growing its own ad-hoc
+//! inverse-based inequality trick (see the decomposition this replaces in
+//! `chips::memory::MemoryChip::eval`).
+//!
+//! A 32-bit quantity is range-checked by decomposing it into two 16-bit
+//! limbs `lo + 2^16 * hi` (see `decompose_u32`) and sending each limb into
+//! this table separately over `Bus::RangeCheck16`; this chip is the
+//! receive side, with a per-row `multiplicity` column counting how many
+//! times its value was looked up.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_from_base, ext_inverse, ext_mul, ext_one};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+
+/// Number of bits range-checked per limb.
+pub const RANGE_CHECK_BITS: u32 = 16;
+/// Number of distinct values in the table: `0..=2^16-1`.
+pub const RANGE_CHECK_SIZE: usize = 1 << RANGE_CHECK_BITS;
+
+/// Range-check trace columns: one row per value in `0..2^16`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RangeCheckColumns<T> {
+    /// This row's value; row `i` holds the value `i`.
+    pub value: T,
+    /// Number of times another chip looked this value up this proof.
+    pub multiplicity: T,
+
+    // === Cross-chip range-check LogUp bus (receive side, see
+    // `machine::check_range_bus_closure`) ===
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// `alpha + value`.
+    pub f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `-multiplicity /
+    /// fingerprint` over this table -- the receive side of
+    /// `Bus::RangeCheck16`. `machine::ZkIrMachine` checks this sums to zero
+    /// against every sender's `range_bus_phi` (`MemoryColumns`,
+    /// `CpuColumns`, and `AluColumns`; see `generate_trace` below).
+    pub phi: [T; 4],
+}
+
+/// Number of columns in the range-check trace.
+pub const RANGE_CHECK_NUM_COLUMNS: usize = 2 + 4 + 4;
+
+impl<T> RangeCheckColumns<T> {
+    pub const NUM_COLUMNS: usize = RANGE_CHECK_NUM_COLUMNS;
+}
+
+impl<T> Borrow<RangeCheckColumns<T>> for [T; RANGE_CHECK_NUM_COLUMNS] {
+    fn borrow(&self) -> &RangeCheckColumns<T> {
+        unsafe { &*(self.as_ptr() as *const RangeCheckColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<RangeCheckColumns<T>> for [T; RANGE_CHECK_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut RangeCheckColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut RangeCheckColumns<T>) }
+    }
+}
+
+/// Range-check chip: a fixed `0..2^16` lookup table.
+pub struct RangeCheckChip;
+
+impl Default for RangeCheckChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RangeCheckChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for RangeCheckChip {
+    fn width(&self) -> usize {
+        RangeCheckColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for RangeCheckChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; RANGE_CHECK_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; RANGE_CHECK_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let local: &RangeCheckColumns<AB::Var> = local_arr.borrow();
+        let next: &RangeCheckColumns<AB::Var> = next_arr.borrow();
+
+        // The table is exactly the enumeration 0..2^16: row 0 is 0, and each
+        // row is one more than the last. Soundness of every lookup against
+        // this table rests entirely on this being the full, gapless range.
+        builder.when_first_row().assert_zero(local.value.into());
+        builder
+            .when_transition()
+            .assert_eq(next.value, local.value.into() + AB::Expr::ONE);
+
+        // === Cross-chip range-check LogUp bus (receive side) ===
+        let (raw_bus_alpha, _raw_bus_beta) = bus_challenges();
+        let bus_alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let f_local = ext_add(&bus_alpha, &ext_from_base(local.value.into()));
+        let f_inv_local: [AB::Expr; 4] = local.f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let neg_multiplicity_local = AB::Expr::ZERO - local.multiplicity.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.phi[i], neg_multiplicity_local.clone() * f_inv_local[i].clone());
+        }
+
+        let f_next = ext_add(&bus_alpha, &ext_from_base(next.value.into()));
+        let f_inv_next: [AB::Expr; 4] = next.f_inv.map(Into::into);
+        let check_next = ext_mul(&f_next, &f_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_next[i].clone(), one[i].clone());
+        }
+        let neg_multiplicity_next = AB::Expr::ZERO - next.multiplicity.into();
+        for i in 0..4 {
+            let term_next = neg_multiplicity_next.clone() * f_inv_next[i].clone();
+            builder
+                .when_transition()
+                .assert_eq(next.phi[i].into() - local.phi[i].into(), term_next);
+        }
+    }
+}
+
+impl RangeCheckChip {
+    /// The receive side of `Bus::RangeCheck16`: this row's value, counted
+    /// `multiplicity` times.
+    pub fn receives<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &RangeCheckColumns<AB::Var>,
+    ) -> Interaction<AB::Expr> {
+        builder.receive(Bus::RangeCheck16, vec![local.value.into()], local.multiplicity.into())
+    }
+
+    /// Generate the range-check trace: every row `i` in `0..2^16` holds
+    /// value `i`, with `multiplicity` set to how many times `i` was sent
+    /// over `Bus::RangeCheck16` by every chip this proof so far wired into
+    /// `machine::ZkIrMachine` -- see `MemoryChip::generate_trace` (the
+    /// address/cycle gaps), `CpuChip::generate_trace` (the shift limbs), and
+    /// `AluChip::generate_trace` (the ADD/SUB result limbs), whose tallies
+    /// `machine::ZkIrMachine::generate_traces` sums element-wise before
+    /// calling this. All three senders also carry their own `range_bus_phi`
+    /// accumulator (`MemoryColumns`/`CpuColumns`/`AluColumns`), checked by
+    /// `machine::ZkIrMachine::check_range_bus_closure` against this chip's
+    /// `phi` -- the tally above only feeds this chip's `multiplicity`
+    /// column, it isn't itself what makes the argument sound.
+    /// `chips::register`'s and `chips::syscall::bn254`'s sends are not
+    /// closed: `RegisterChip` is in `ZkIrMachine` but carries no
+    /// `range_bus_phi` and hands over no tally, and `Bn254ScalarChip` is not
+    /// in `ZkIrMachine` at all.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `MemoryChip::generate_trace`: the bus `phi` column goes through
+    /// `crate::EF`.
+    pub fn generate_trace(&self, multiplicities: &[u64; RANGE_CHECK_SIZE]) -> RowMajorMatrix<crate::F> {
+        type F = crate::F;
+        let (raw_bus_alpha, _raw_bus_beta) = bus_challenges();
+        let bus_alpha = raw_bus_alpha.map(F::from_canonical_u32);
+
+        let mut values = vec![F::ZERO; RANGE_CHECK_SIZE * RangeCheckColumns::<F>::NUM_COLUMNS];
+        let mut phi = [F::ZERO; 4];
+        for i in 0..RANGE_CHECK_SIZE {
+            let row_offset = i * RangeCheckColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; RANGE_CHECK_NUM_COLUMNS] = (&mut values
+                [row_offset..row_offset + RangeCheckColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+            let cols: &mut RangeCheckColumns<F> = row.borrow_mut();
+            cols.value = F::from_canonical_usize(i);
+            cols.multiplicity = F::from_canonical_u64(multiplicities[i]);
+
+            let f = ext_add(&bus_alpha, &ext_from_base(cols.value));
+            let f_inv = ext_inverse(f);
+            let neg_multiplicity = F::ZERO - cols.multiplicity;
+            for j in 0..4 {
+                phi[j] = phi[j] + neg_multiplicity * f_inv[j];
+            }
+            cols.f_inv = f_inv;
+            cols.phi = phi;
+        }
+        RowMajorMatrix::new(values, RangeCheckColumns::<F>::NUM_COLUMNS)
+    }
+}
+
+/// Decompose a 32-bit value into two 16-bit limbs `(lo, hi)` such that
+/// `value == lo + 2^16 * hi` -- the shape every sender of `Bus::RangeCheck16`
+/// range-checks a 32-bit quantity through.
+pub fn decompose_u32(value: u32) -> (u32, u32) {
+    (value & 0xFFFF, value >> RANGE_CHECK_BITS)
+}
@@ -0,0 +1,501 @@
+//! Register Chip implementation
+//!
+//! Enforces register-file consistency using the same sorted-trace approach
+//! `chips::memory::MemoryChip` uses for memory: register accesses are sorted
+//! by `(reg_index, cycle)`, and constraints ensure that reads return the
+//! most recently written value. A single CPU step can touch up to three
+//! registers in the same cycle (an `rs1` read, an `rs2` read, and an `rd`
+//! write), so `cycle` here is really the monotonic access-sequence number
+//! `RegisterAccess::cycle` documents (`step.cycle * 3 + slot`), not the raw
+//! CPU cycle -- everything downstream treats it exactly like
+//! `MemoryColumns::cycle` otherwise.
+//!
+//! As with memory, sorting alone only proves something about *an* ordering
+//! of the accesses; the permutation (grand-product) argument between the
+//! execution-order log and the register-sorted log closes that gap, and for
+//! the same reason `MemoryChip` gives, lives in the degree-4 extension
+//! `GF(p^4)`.
+//!
+//! This chip additionally hard-constrains that register 0 is always zero
+//! (on every sorted-order row, not just reads), which is what finally
+//! backs the comment in `chips::cpu::air::CpuChip::eval` that used to just
+//! assert it in prose.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_inverse, ext_mul, ext_one, ext_sub, ext_to_generic, fingerprint};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+use crate::chips::range::{self, RANGE_CHECK_BITS};
+use crate::trace::ExecutionTrace;
+
+/// Register trace columns
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RegisterColumns<T> {
+    /// Register index (sorted ordering)
+    pub reg_index: T,
+    /// Access sequence number when this access occurred (sorted ordering)
+    pub cycle: T,
+    /// Value read or written (sorted ordering)
+    pub value: T,
+    /// 1 if write, 0 if read (sorted ordering)
+    pub is_write: T,
+
+    // Helper columns for constraints
+    /// 1 if this row has the same register index as the next row
+    pub same_reg_as_next: T,
+    /// Low 16 bits of `next.reg_index - reg_index - 1`, witnessed when
+    /// `same_reg_as_next == 0` (meaningless, left zero, otherwise); see
+    /// `chips::range`.
+    pub idx_diff_lo: T,
+    /// High 16 bits of `next.reg_index - reg_index - 1`.
+    pub idx_diff_hi: T,
+    /// Low 16 bits of `next.cycle - cycle - 1`, witnessed when
+    /// `same_reg_as_next == 1` (meaningless, left zero, otherwise).
+    pub cycle_diff_lo: T,
+    /// High 16 bits of `next.cycle - cycle - 1`.
+    pub cycle_diff_hi: T,
+
+    // === Execution-order side of the permutation argument ===
+    /// Register index of the access at this row index in execution order
+    pub exec_reg_index: T,
+    /// Sequence number of the access at this row index in execution order
+    pub exec_cycle: T,
+    /// Value of the access at this row index in execution order
+    pub exec_value: T,
+    /// Is-write flag of the access at this row index in execution order
+    pub exec_is_write: T,
+
+    // === Grand-product accumulators, degree-4 extension field ===
+    /// Running product of `(alpha - f)` over the sorted ordering, up to and
+    /// including this row.
+    pub acc_sorted: [T; 4],
+    /// Running product of `(alpha - f)` over the execution-order ordering,
+    /// up to and including this row.
+    pub acc_exec: [T; 4],
+
+    // === Cross-chip register LogUp bus (receive side, see `machine`) ===
+    /// 1 if this row is a real (non-padding) access and so participates in
+    /// the bus with the CPU chip; 0 for trace padding beyond the real
+    /// accesses.
+    pub is_real: T,
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// over the execution-order columns (`exec_*`), matching one of the
+    /// tuples the CPU chip sent for the same access.
+    pub reg_bus_f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `multiplicity /
+    /// fingerprint` over this chip's rows -- the receive side of the bus
+    /// (multiplicity `-1` per real access). `machine::ZkIrMachine` checks
+    /// this sums to zero against the CPU chip's send side.
+    pub reg_bus_phi: [T; 4],
+
+    // === Register-zero hard constraint ===
+    /// Witnessed inverse of `reg_index`, meaningless (left zero) when
+    /// `reg_index == 0`. Used only to pin down `is_r0_access` below; see
+    /// the constraint in `eval`.
+    pub reg_index_inv: T,
+    /// 1 iff `reg_index == 0` on this (sorted-order) row, pinned down by
+    /// `reg_index_inv` above -- forces `value == 0` below, the hard
+    /// constraint that register 0 is always zero.
+    pub is_r0_access: T,
+}
+
+/// Number of columns in the register trace
+pub const REGISTER_NUM_COLUMNS: usize = 4 + 5 + 4 + 4 + 4 + 1 + 4 + 4 + 2;
+
+impl<T> RegisterColumns<T> {
+    pub const NUM_COLUMNS: usize = REGISTER_NUM_COLUMNS;
+}
+
+impl<T> Borrow<RegisterColumns<T>> for [T; REGISTER_NUM_COLUMNS] {
+    fn borrow(&self) -> &RegisterColumns<T> {
+        unsafe { &*(self.as_ptr() as *const RegisterColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<RegisterColumns<T>> for [T; REGISTER_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut RegisterColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut RegisterColumns<T>) }
+    }
+}
+
+/// Register Chip enforcing register-file read/write consistency
+pub struct RegisterChip {
+    /// Fiat-Shamir challenge `alpha`, in `crate::EF` (see `chips::ext`).
+    ///
+    /// TODO: same stance as `MemoryChip::alpha` -- these should be drawn by
+    /// the verifier once the prover module exists; derived deterministically
+    /// for now so the chip is self-contained.
+    pub alpha: crate::EF,
+    /// Fiat-Shamir challenge `beta`, in `crate::EF`.
+    pub beta: crate::EF,
+}
+
+impl Default for RegisterChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterChip {
+    pub fn new() -> Self {
+        // Distinct domain separator from `MemoryChip::new`'s -- a different
+        // chip's internal permutation argument has no reason to share
+        // challenges.
+        let mut t = crate::chips::transcript::Transcript::new(b"zkir-prover/register-chip/v1");
+        Self {
+            alpha: t.draw_ext_challenge(),
+            beta: t.draw_ext_challenge(),
+        }
+    }
+
+    fn alpha_ext<T: FieldAlgebra>(&self) -> [T; 4] {
+        ext_to_generic(self.alpha)
+    }
+
+    fn beta_ext<T: FieldAlgebra>(&self) -> [T; 4] {
+        ext_to_generic(self.beta)
+    }
+}
+
+impl<F: Field> BaseAir<F> for RegisterChip {
+    fn width(&self) -> usize {
+        RegisterColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for RegisterChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; REGISTER_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; REGISTER_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let local: &RegisterColumns<AB::Var> = local_arr.borrow();
+        let next: &RegisterColumns<AB::Var> = next_arr.borrow();
+
+        // Boolean constraints
+        builder.assert_zero(local.is_write.into() * (AB::Expr::ONE - local.is_write.into()));
+        builder.assert_zero(
+            local.same_reg_as_next.into() * (AB::Expr::ONE - local.same_reg_as_next.into()),
+        );
+
+        // Register-index ordering: indices are non-decreasing
+        // When same_reg_as_next = 1: next.reg_index = local.reg_index
+        builder
+            .when_transition()
+            .when(local.same_reg_as_next)
+            .assert_eq(next.reg_index, local.reg_index);
+
+        // When same_reg_as_next = 0: next.reg_index > local.reg_index, proven
+        // by range-checking `next.reg_index - local.reg_index - 1` as a
+        // valid (non-negative) 32-bit value, exactly like `MemoryChip`.
+        //
+        // Gated by `next.is_real`: `generate_trace` leaves `same_reg_as_next`
+        // and both diff columns at zero on the last real row, so without
+        // this gate the transition into the all-zero padding row would
+        // assert `0 - reg_index - 1 == 0`, which is false for any real
+        // trace whose access count isn't already a power of two -- the same
+        // completeness bug `MemoryChip` had.
+        let two_pow_16 = AB::Expr::from_canonical_u32(1 << RANGE_CHECK_BITS);
+        let idx_diff_composed: AB::Expr =
+            local.idx_diff_lo.into() + two_pow_16.clone() * local.idx_diff_hi.into();
+        builder
+            .when_transition()
+            .when(next.is_real.into())
+            .when(AB::Expr::ONE - local.same_reg_as_next.into())
+            .assert_eq(
+                next.reg_index.into() - local.reg_index.into() - AB::Expr::ONE,
+                idx_diff_composed,
+            );
+
+        // Sequence ordering within the same register: strictly increasing,
+        // proven the same way via `cycle_diff_lo`/`_hi`, gated by
+        // `next.is_real` for the same padding-boundary reason.
+        let cycle_diff_composed: AB::Expr =
+            local.cycle_diff_lo.into() + two_pow_16 * local.cycle_diff_hi.into();
+        builder
+            .when_transition()
+            .when(next.is_real.into())
+            .when(local.same_reg_as_next)
+            .assert_eq(
+                next.cycle.into() - local.cycle.into() - AB::Expr::ONE,
+                cycle_diff_composed,
+            );
+
+        // Read consistency: reads return last written value
+        let next_is_read: AB::Expr = AB::Expr::ONE - next.is_write.into();
+        builder
+            .when_transition()
+            .when(local.same_reg_as_next)
+            .when(next_is_read)
+            .assert_eq(next.value, local.value);
+
+        // === Register zero: r0 is hard-wired to always read/write as 0 ===
+        //
+        // `is_r0_access` is pinned to exactly `[reg_index == 0]` by the two
+        // constraints below (the standard witnessed-inverse zero-check: if
+        // `reg_index != 0` the first constraint forces `is_r0_access = 0`
+        // and the second then forces `reg_index_inv` to really be its
+        // inverse; if `reg_index == 0` the first constraint is vacuous and
+        // the second pins `is_r0_access = 1`), then used to force `value =
+        // 0` whenever it holds.
+        builder.assert_zero(local.reg_index.into() * local.is_r0_access.into());
+        builder.assert_one(
+            local.reg_index.into() * local.reg_index_inv.into() + local.is_r0_access.into(),
+        );
+        builder.assert_zero(local.is_r0_access.into() * local.value.into());
+
+        // === Permutation argument between execution order and sorted order ===
+        let alpha = self.alpha_ext::<AB::Expr>();
+        let beta = self.beta_ext::<AB::Expr>();
+
+        let f_sorted_local = fingerprint(
+            &beta,
+            local.reg_index.into(),
+            local.cycle.into(),
+            local.value.into(),
+            local.is_write.into(),
+        );
+        let f_exec_local = fingerprint(
+            &beta,
+            local.exec_reg_index.into(),
+            local.exec_cycle.into(),
+            local.exec_value.into(),
+            local.exec_is_write.into(),
+        );
+        let term_sorted_local = ext_sub(&alpha, &f_sorted_local);
+        let term_exec_local = ext_sub(&alpha, &f_exec_local);
+
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.acc_sorted[i], term_sorted_local[i].clone());
+            builder
+                .when_first_row()
+                .assert_eq(local.acc_exec[i], term_exec_local[i].clone());
+        }
+
+        let acc_sorted_local: [AB::Expr; 4] = local.acc_sorted.map(Into::into);
+        let acc_exec_local: [AB::Expr; 4] = local.acc_exec.map(Into::into);
+
+        let f_sorted_next = fingerprint(
+            &beta,
+            next.reg_index.into(),
+            next.cycle.into(),
+            next.value.into(),
+            next.is_write.into(),
+        );
+        let f_exec_next = fingerprint(
+            &beta,
+            next.exec_reg_index.into(),
+            next.exec_cycle.into(),
+            next.exec_value.into(),
+            next.exec_is_write.into(),
+        );
+        let term_sorted_next = ext_sub(&alpha, &f_sorted_next);
+        let term_exec_next = ext_sub(&alpha, &f_exec_next);
+
+        let next_acc_sorted = ext_mul(&acc_sorted_local, &term_sorted_next);
+        let next_acc_exec = ext_mul(&acc_exec_local, &term_exec_next);
+
+        for i in 0..4 {
+            builder
+                .when_transition()
+                .assert_eq(next.acc_sorted[i], next_acc_sorted[i].clone());
+            builder
+                .when_transition()
+                .assert_eq(next.acc_exec[i], next_acc_exec[i].clone());
+        }
+
+        for i in 0..4 {
+            builder
+                .when_last_row()
+                .assert_eq(local.acc_sorted[i], local.acc_exec[i]);
+        }
+
+        // === Cross-chip register LogUp bus ===
+        builder.assert_zero(local.is_real.into() * (AB::Expr::ONE - local.is_real.into()));
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let bus_beta: [AB::Expr; 4] = raw_bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let bus_f_local = fingerprint(
+            &bus_beta,
+            local.exec_reg_index.into(),
+            local.exec_cycle.into(),
+            local.exec_value.into(),
+            local.exec_is_write.into(),
+        );
+        let bus_f_local = ext_add(&bus_alpha, &bus_f_local);
+
+        let bus_f_inv_local: [AB::Expr; 4] = local.reg_bus_f_inv.map(Into::into);
+        let check_local = ext_mul(&bus_f_local, &bus_f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let neg_is_real_local = AB::Expr::ZERO - local.is_real.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.reg_bus_phi[i], neg_is_real_local.clone() * bus_f_inv_local[i].clone());
+        }
+
+        let neg_is_real_next = AB::Expr::ZERO - next.is_real.into();
+        let bus_f_inv_next: [AB::Expr; 4] = next.reg_bus_f_inv.map(Into::into);
+        for i in 0..4 {
+            let term_next = neg_is_real_next.clone() * bus_f_inv_next[i].clone();
+            builder.when_transition().assert_eq(
+                next.reg_bus_phi[i].into() - local.reg_bus_phi[i].into(),
+                term_next,
+            );
+        }
+    }
+}
+
+impl RegisterChip {
+    /// The interaction bus tuples this row sends: the two 16-bit limbs of
+    /// whichever strictly-increasing gap (register index or sequence number)
+    /// this row actually witnesses, range-checked through
+    /// `Bus::RangeCheck16` exactly like `MemoryChip::sends`.
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &RegisterColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        let is_idx_gap: AB::Expr = AB::Expr::ONE - local.same_reg_as_next.into();
+        let is_cycle_gap: AB::Expr = local.same_reg_as_next.into();
+
+        vec![
+            builder.send(Bus::RangeCheck16, vec![local.idx_diff_lo.into()], is_idx_gap.clone()),
+            builder.send(Bus::RangeCheck16, vec![local.idx_diff_hi.into()], is_idx_gap),
+            builder.send(Bus::RangeCheck16, vec![local.cycle_diff_lo.into()], is_cycle_gap.clone()),
+            builder.send(Bus::RangeCheck16, vec![local.cycle_diff_hi.into()], is_cycle_gap),
+        ]
+    }
+
+    /// Generate the register trace. Row `i` holds both the `i`-th access in
+    /// register-sorted order and the `i`-th access in execution order, plus
+    /// the running grand-product accumulators for each.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `MemoryChip::generate_trace`: the cross-chip bus columns below go
+    /// through `crate::EF`.
+    pub fn generate_trace(&self, trace: &ExecutionTrace) -> RowMajorMatrix<crate::F> {
+        type F = crate::F;
+
+        let sorted = trace.sorted_register_log();
+        let exec = &trace.register_log;
+        debug_assert_eq!(sorted.len(), exec.len());
+
+        let num_accesses = sorted.len();
+        let trace_len = num_accesses.next_power_of_two().max(2);
+
+        let mut values = vec![F::ZERO; trace_len * RegisterColumns::<F>::NUM_COLUMNS];
+
+        let alpha = self.alpha_ext::<F>();
+        let beta = self.beta_ext::<F>();
+        let mut acc_sorted = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        let mut acc_exec = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let bus_beta = raw_bus_beta.map(F::from_canonical_u32);
+        let mut reg_bus_phi = [F::ZERO; 4];
+
+        for i in 0..trace_len {
+            let row_offset = i * RegisterColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; REGISTER_NUM_COLUMNS] =
+                (&mut values[row_offset..row_offset + RegisterColumns::<F>::NUM_COLUMNS])
+                    .try_into()
+                    .unwrap();
+            let cols: &mut RegisterColumns<F> = row.borrow_mut();
+
+            // Padding rows beyond the real accesses repeat an all-zero
+            // access on both sides, same rationale as `MemoryChip`.
+            if let Some(access) = sorted.get(i) {
+                cols.reg_index = F::from_canonical_u32(access.reg_index);
+                cols.cycle = F::from_canonical_u64(access.cycle);
+                cols.value = F::from_canonical_u32(access.value);
+                cols.is_write = if access.is_write { F::ONE } else { F::ZERO };
+
+                if access.reg_index == 0 {
+                    cols.is_r0_access = F::ONE;
+                } else {
+                    cols.reg_index_inv = F::from_canonical_u32(access.reg_index).inverse();
+                }
+
+                if let Some(next_access) = sorted.get(i + 1) {
+                    if next_access.reg_index == access.reg_index {
+                        cols.same_reg_as_next = F::ONE;
+                        let diff = (next_access.cycle - access.cycle - 1) as u32;
+                        let (lo, hi) = range::decompose_u32(diff);
+                        cols.cycle_diff_lo = F::from_canonical_u32(lo);
+                        cols.cycle_diff_hi = F::from_canonical_u32(hi);
+                    } else {
+                        let diff = next_access.reg_index - access.reg_index - 1;
+                        let (lo, hi) = range::decompose_u32(diff);
+                        cols.idx_diff_lo = F::from_canonical_u32(lo);
+                        cols.idx_diff_hi = F::from_canonical_u32(hi);
+                    }
+                }
+            } else {
+                // Padding row: reg_index = 0, so the r0 hard constraint
+                // still needs `is_r0_access = 1` to hold.
+                cols.is_r0_access = F::ONE;
+            }
+
+            if let Some(access) = exec.get(i) {
+                cols.exec_reg_index = F::from_canonical_u32(access.reg_index);
+                cols.exec_cycle = F::from_canonical_u64(access.cycle);
+                cols.exec_value = F::from_canonical_u32(access.value);
+                cols.exec_is_write = if access.is_write { F::ONE } else { F::ZERO };
+                cols.is_real = F::ONE;
+            }
+
+            let bus_f = fingerprint(
+                &bus_beta,
+                cols.exec_reg_index,
+                cols.exec_cycle,
+                cols.exec_value,
+                cols.exec_is_write,
+            );
+            let bus_f = ext_add(&bus_alpha, &bus_f);
+            let bus_f_inv = ext_inverse(bus_f);
+            let neg_is_real = F::ZERO - cols.is_real;
+            for j in 0..4 {
+                reg_bus_phi[j] = reg_bus_phi[j] + neg_is_real * bus_f_inv[j];
+            }
+            cols.reg_bus_f_inv = bus_f_inv;
+            cols.reg_bus_phi = reg_bus_phi;
+
+            let f_sorted = fingerprint(&beta, cols.reg_index, cols.cycle, cols.value, cols.is_write);
+            let f_exec = fingerprint(
+                &beta,
+                cols.exec_reg_index,
+                cols.exec_cycle,
+                cols.exec_value,
+                cols.exec_is_write,
+            );
+            acc_sorted = ext_mul(&acc_sorted, &ext_sub(&alpha, &f_sorted));
+            acc_exec = ext_mul(&acc_exec, &ext_sub(&alpha, &f_exec));
+
+            cols.acc_sorted = acc_sorted;
+            cols.acc_exec = acc_exec;
+        }
+
+        debug_assert_eq!(acc_sorted, acc_exec);
+
+        RowMajorMatrix::new(values, RegisterColumns::<F>::NUM_COLUMNS)
+    }
+}
@@ -0,0 +1,211 @@
+//! Power-of-two lookup chip: enumerates `shift_amount in 0..32` alongside
+//! `pow = 2^shift_amount`, so the CPU chip can prove a witnessed `pow` is
+//! really `1 << shift_amount` by looking the pair up through the
+//! interaction bus instead of asserting it directly (which would need a
+//! non-polynomial exponentiation).
+//!
+//! RISC-V shift amounts are always masked to 5 bits at decode time (see
+//! `chips::cpu::trace`), so every real lookup lands in `0..32` and the
+//! "`shift_amount >= 32` forces `pow = 0`" case the request describes is
+//! defensive but unreachable through that decode path; this chip doesn't
+//! need a sentinel row for it.
+//!
+//! The `f_inv`/`phi` columns below close this table's LogUp argument against
+//! `CpuColumns::shift_bus_phi`, the only sender wired into `machine::ZkIrMachine`
+//! today (see `machine::ZkIrMachine::check_shift_bus_closure`); `receives`
+//! still builds the generic interaction-bus tuple too, but like every other
+//! chip's `receives`/`sends`, nothing aggregates that return value.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::ext::{ext_add, ext_inverse, ext_mul, ext_one, fingerprint_n};
+use crate::chips::interaction::{bus_challenges, Bus, Interaction, InteractionBuilder};
+
+/// Number of shift amounts covered: `0..32`.
+pub const SHIFT_TABLE_SIZE: usize = 32;
+
+/// Shift-power trace columns: one row per `shift_amount` in `0..32`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShiftPowColumns<T> {
+    /// This row's shift amount; row `i` holds `i`.
+    pub shift_amount: T,
+    /// `2^shift_amount`.
+    pub pow: T,
+    /// Number of times another chip looked this pair up this proof.
+    pub multiplicity: T,
+
+    // === Cross-chip shift-power LogUp bus (receive side, see
+    // `machine::check_shift_bus_closure`) ===
+    /// Inverse, in the degree-4 extension, of this row's bus fingerprint
+    /// `alpha + fingerprint_n(beta, (shift_amount, pow))`.
+    pub f_inv: [T; 4],
+    /// Running sum, in the degree-4 extension, of `-multiplicity /
+    /// fingerprint` over this table -- the receive side of `Bus::ShiftPow`.
+    /// `machine::ZkIrMachine` checks this sums to zero against
+    /// `CpuColumns::shift_bus_phi`.
+    pub phi: [T; 4],
+}
+
+/// Number of columns in the shift-power trace.
+pub const SHIFT_POW_NUM_COLUMNS: usize = 3 + 4 + 4;
+
+impl<T> ShiftPowColumns<T> {
+    pub const NUM_COLUMNS: usize = SHIFT_POW_NUM_COLUMNS;
+}
+
+impl<T> Borrow<ShiftPowColumns<T>> for [T; SHIFT_POW_NUM_COLUMNS] {
+    fn borrow(&self) -> &ShiftPowColumns<T> {
+        unsafe { &*(self.as_ptr() as *const ShiftPowColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<ShiftPowColumns<T>> for [T; SHIFT_POW_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut ShiftPowColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut ShiftPowColumns<T>) }
+    }
+}
+
+/// Shift-power chip: a fixed `(shift_amount, 2^shift_amount)` lookup table.
+pub struct ShiftPowChip;
+
+impl Default for ShiftPowChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShiftPowChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for ShiftPowChip {
+    fn width(&self) -> usize {
+        ShiftPowColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for ShiftPowChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; SHIFT_POW_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; SHIFT_POW_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let local: &ShiftPowColumns<AB::Var> = local_arr.borrow();
+        let next: &ShiftPowColumns<AB::Var> = next_arr.borrow();
+
+        // Row 0 pins the base case; every following row doubles `pow` and
+        // increments `shift_amount`, which forces row `i` to hold exactly
+        // `(i, 2^i)` all the way down the table -- the same "boundary +
+        // transition enumeration" shape `chips::range::RangeCheckChip` uses.
+        builder.when_first_row().assert_zero(local.shift_amount.into());
+        builder.when_first_row().assert_one(local.pow.into());
+        builder
+            .when_transition()
+            .assert_eq(next.shift_amount, local.shift_amount.into() + AB::Expr::ONE);
+        builder
+            .when_transition()
+            .assert_eq(next.pow, local.pow.into() * AB::Expr::from_canonical_u32(2));
+
+        // === Cross-chip shift-power LogUp bus (receive side) ===
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha: [AB::Expr; 4] = raw_bus_alpha.map(AB::Expr::from_canonical_u32);
+        let bus_beta: [AB::Expr; 4] = raw_bus_beta.map(AB::Expr::from_canonical_u32);
+        let one = ext_one::<AB::Expr>();
+
+        let values_local = [local.shift_amount.into(), local.pow.into()];
+        let f_local = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &values_local));
+        let f_inv_local: [AB::Expr; 4] = local.f_inv.map(Into::into);
+        let check_local = ext_mul(&f_local, &f_inv_local);
+        for i in 0..4 {
+            builder.assert_eq(check_local[i].clone(), one[i].clone());
+        }
+
+        let neg_multiplicity_local = AB::Expr::ZERO - local.multiplicity.into();
+        for i in 0..4 {
+            builder
+                .when_first_row()
+                .assert_eq(local.phi[i], neg_multiplicity_local.clone() * f_inv_local[i].clone());
+        }
+
+        let values_next = [next.shift_amount.into(), next.pow.into()];
+        let f_next = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &values_next));
+        let f_inv_next: [AB::Expr; 4] = next.f_inv.map(Into::into);
+        let check_next = ext_mul(&f_next, &f_inv_next);
+        for i in 0..4 {
+            builder.when_transition().assert_eq(check_next[i].clone(), one[i].clone());
+        }
+        let neg_multiplicity_next = AB::Expr::ZERO - next.multiplicity.into();
+        for i in 0..4 {
+            let term_next = neg_multiplicity_next.clone() * f_inv_next[i].clone();
+            builder
+                .when_transition()
+                .assert_eq(next.phi[i].into() - local.phi[i].into(), term_next);
+        }
+    }
+}
+
+impl ShiftPowChip {
+    /// The receive side of `Bus::ShiftPow`: this row's `(shift_amount, pow)`
+    /// pair, counted `multiplicity` times.
+    pub fn receives<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &ShiftPowColumns<AB::Var>,
+    ) -> Interaction<AB::Expr> {
+        builder.receive(
+            Bus::ShiftPow,
+            vec![local.shift_amount.into(), local.pow.into()],
+            local.multiplicity.into(),
+        )
+    }
+
+    /// Generate the shift-power trace: row `i` in `0..32` holds
+    /// `(shift_amount, pow) = (i, 2^i)`, with `multiplicity` set from
+    /// `multiplicities[i]` -- see `chips::cpu::trace::generate_cpu_trace`,
+    /// whose tally this is, threaded through `machine`.
+    ///
+    /// Fixed to `crate::F` rather than generic over `Field`, like
+    /// `MemoryChip::generate_trace`/`RangeCheckChip::generate_trace`: the bus
+    /// `phi` column goes through `crate::EF`.
+    pub fn generate_trace(&self, multiplicities: &[u64; SHIFT_TABLE_SIZE]) -> RowMajorMatrix<crate::F> {
+        type F = crate::F;
+        let (raw_bus_alpha, raw_bus_beta) = bus_challenges();
+        let bus_alpha = raw_bus_alpha.map(F::from_canonical_u32);
+        let bus_beta = raw_bus_beta.map(F::from_canonical_u32);
+
+        let mut values = vec![F::ZERO; SHIFT_TABLE_SIZE * ShiftPowColumns::<F>::NUM_COLUMNS];
+        let mut phi = [F::ZERO; 4];
+        for i in 0..SHIFT_TABLE_SIZE {
+            let row_offset = i * ShiftPowColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; SHIFT_POW_NUM_COLUMNS] = (&mut values
+                [row_offset..row_offset + ShiftPowColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+            let cols: &mut ShiftPowColumns<F> = row.borrow_mut();
+            cols.shift_amount = F::from_canonical_usize(i);
+            cols.pow = F::from_wrapped_u64(1u64 << i);
+            cols.multiplicity = F::from_canonical_u64(multiplicities[i]);
+
+            let f = ext_add(&bus_alpha, &fingerprint_n(&bus_beta, &[cols.shift_amount, cols.pow]));
+            let f_inv = ext_inverse(f);
+            let neg_multiplicity = F::ZERO - cols.multiplicity;
+            for j in 0..4 {
+                phi[j] = phi[j] + neg_multiplicity * f_inv[j];
+            }
+            cols.f_inv = f_inv;
+            cols.phi = phi;
+        }
+        RowMajorMatrix::new(values, ShiftPowColumns::<F>::NUM_COLUMNS)
+    }
+}
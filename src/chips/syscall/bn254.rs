@@ -0,0 +1,605 @@
+//! bn254 scalar field arithmetic chip
+//!
+//! Implements `add`, `mul`, and multiply-accumulate (`mac`) over the bn254
+//! scalar field `F_r`, exposed as the `Bn254ScalarAdd` / `Bn254ScalarMul` /
+//! `Bn254ScalarMac` syscalls. All three reduce to one identity:
+//!
+//! ```text
+//! a * b' + c' = q * p + result   (as integers, not just mod 2^256)
+//! ```
+//!
+//! where `p` is the bn254 scalar modulus and `(b', c')` are chosen per op so
+//! the same schoolbook multiply-accumulate covers addition too:
+//! - add: `b' = 1`, `c' = b`  (so `a*1 + b = a + b`)
+//! - mul: `b' = b`, `c' = 0`
+//! - mac: `b' = b`, `c' = c`
+//!
+//! Field elements are represented as 8 limbs of 32 bits, matching
+//! `crate::WORD_SIZE`. The limbwise product is accumulated into 15
+//! positions (`2*8 - 1`), each reduced mod `2^32` with a witnessed carry
+//! into the next position; the final position's running value must be
+//! exactly zero (not just zero mod `2^32`), which is what pins the identity
+//! down as an integer equation rather than a mod-`2^256` one. `result` is
+//! additionally bound to `< p` by the same borrow-subtraction technique
+//! against `p - 1`, range-checked limb by limb through `Bus::RangeCheck16`
+//! (see `eval`/`sends`) -- without it a non-reduced `result` could still
+//! satisfy the limb identity above.
+//!
+//! The other syscall chips (`Poseidon2Chip`, `KeccakChip`) read their inputs
+//! directly out of `SyscallRecord.inputs` rather than resolving pointers
+//! through the memory log, and this chip follows the same convention: the
+//! caller is expected to have already materialized `a`, `b`, and `c` as
+//! 8-limb words (24 words total, `c` all-zero for add/mul) rather than
+//! passing raw pointers, since there's no memory-read helper yet that maps
+//! a pointer to a value at a given cycle.
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use super::SyscallChip;
+use crate::chips::interaction::{Bus, Interaction, InteractionBuilder};
+use crate::chips::range;
+use crate::trace::{SyscallCode, SyscallRecord};
+
+/// Number of 32-bit limbs in one bn254 scalar field element.
+pub const BN254_SCALAR_LIMBS: usize = 8;
+/// Number of limb positions in an unreduced 8x8 schoolbook product
+/// (`2 * BN254_SCALAR_LIMBS - 1`).
+pub const BN254_PRODUCT_LIMBS: usize = 2 * BN254_SCALAR_LIMBS - 1;
+/// Number of carries needed to propagate the product through all but the
+/// last limb position (the last position must land on exactly zero).
+pub const BN254_CARRY_LIMBS: usize = BN254_PRODUCT_LIMBS - 1;
+
+/// bn254 scalar field modulus, little-endian 32-bit limbs:
+/// `21888242871839275222246405745257275088548364400416034343698204186575808495617`.
+pub const BN254_SCALAR_MODULUS: [u32; BN254_SCALAR_LIMBS] = [
+    0xf0000001, 0x43e1f593, 0x79b97091, 0x2833e848, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+
+/// bn254 scalar chip trace columns
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bn254ScalarColumns<T> {
+    /// Cycle when the syscall was invoked
+    pub cycle: T,
+    /// 1 if this row is an add
+    pub is_add: T,
+    /// 1 if this row is a mul
+    pub is_mul: T,
+    /// 1 if this row is a mac
+    pub is_mac: T,
+
+    /// First operand limbs
+    pub a: [T; BN254_SCALAR_LIMBS],
+    /// Second operand limbs (the addend for add, the multiplicand for mul/mac)
+    pub b: [T; BN254_SCALAR_LIMBS],
+    /// Accumulator operand limbs (only meaningful for mac)
+    pub c: [T; BN254_SCALAR_LIMBS],
+    /// Witnessed quotient limbs, `q` in `a*b' + c' = q*p + result`
+    pub quotient: [T; BN254_SCALAR_LIMBS],
+    /// Result limbs
+    pub result: [T; BN254_SCALAR_LIMBS],
+    /// Carries propagating the limbwise product/quotient difference
+    pub carry: [T; BN254_CARRY_LIMBS],
+
+    /// 1 for a genuine call, 0 for power-of-two padding.
+    pub is_real: T,
+    /// Witnessed limbs of `(p - 1) - result`, proving `result < p`: each
+    /// limb is forced to be a valid 32-bit value (range-checked via
+    /// `lt_diff_lo`/`lt_diff_hi` below), so the 256-bit subtraction it
+    /// decomposes couldn't have underflowed.
+    pub lt_diff: [T; BN254_SCALAR_LIMBS],
+    /// Per-limb borrow out of the `(p - 1) - result` subtraction. The last
+    /// limb's borrow-out has no column -- it's asserted zero directly in
+    /// `eval`, the same way `carry`'s last position is asserted zero
+    /// instead of stored, which is what pins this down as `result < p`
+    /// rather than `result < p` only mod `2^256`.
+    pub lt_borrow: [T; BN254_CARRY_LIMBS],
+    /// Low 16 bits of each `lt_diff` limb, range-checked via `Bus::RangeCheck16`.
+    pub lt_diff_lo: [T; BN254_SCALAR_LIMBS],
+    /// High 16 bits of each `lt_diff` limb, range-checked via `Bus::RangeCheck16`.
+    pub lt_diff_hi: [T; BN254_SCALAR_LIMBS],
+
+    /// Row-unique nonce for this chip's interaction bus receives
+    pub nonce: T,
+}
+
+/// Number of columns in the bn254 scalar chip trace
+pub const BN254_SCALAR_NUM_COLUMNS: usize =
+    4 + BN254_SCALAR_LIMBS * 8 + BN254_CARRY_LIMBS * 2 + 2;
+
+impl<T> Bn254ScalarColumns<T> {
+    pub const NUM_COLUMNS: usize = BN254_SCALAR_NUM_COLUMNS;
+}
+
+impl<T> Borrow<Bn254ScalarColumns<T>> for [T; BN254_SCALAR_NUM_COLUMNS] {
+    fn borrow(&self) -> &Bn254ScalarColumns<T> {
+        unsafe { &*(self.as_ptr() as *const Bn254ScalarColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<Bn254ScalarColumns<T>> for [T; BN254_SCALAR_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut Bn254ScalarColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut Bn254ScalarColumns<T>) }
+    }
+}
+
+/// bn254 scalar field arithmetic chip (add / mul / mac)
+pub struct Bn254ScalarChip;
+
+impl Default for Bn254ScalarChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bn254ScalarChip {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn modulus<T: FieldAlgebra>() -> [T; BN254_SCALAR_LIMBS] {
+        BN254_SCALAR_MODULUS.map(T::from_canonical_u32)
+    }
+
+    /// `p - 1`, limbwise (the low limb is nonzero, so this never borrows).
+    fn modulus_minus_one<T: FieldAlgebra>() -> [T; BN254_SCALAR_LIMBS] {
+        let mut limbs = BN254_SCALAR_MODULUS;
+        limbs[0] -= 1;
+        limbs.map(T::from_canonical_u32)
+    }
+}
+
+impl SyscallChip for Bn254ScalarChip {
+    fn syscall_code(&self) -> u32 {
+        // One chip serves three syscall codes; this is the representative
+        // one used for bookkeeping (e.g. `constraints_per_call` estimates).
+        SyscallCode::Bn254ScalarMac as u32
+    }
+
+    fn constraints_per_call(&self) -> usize {
+        // Dominated by the 8x8 schoolbook product: ~64 multiplications for
+        // a*b plus ~64 more for q*p, plus carry propagation.
+        150
+    }
+}
+
+impl<F: Field> BaseAir<F> for Bn254ScalarChip {
+    fn width(&self) -> usize {
+        Bn254ScalarColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for Bn254ScalarChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let local_arr: &[AB::Var; BN254_SCALAR_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let local: &Bn254ScalarColumns<AB::Var> = local_arr.borrow();
+
+        // Each flag is boolean, and at most one is set.
+        builder.assert_zero(local.is_add.into() * (AB::Expr::ONE - local.is_add.into()));
+        builder.assert_zero(local.is_mul.into() * (AB::Expr::ONE - local.is_mul.into()));
+        builder.assert_zero(local.is_mac.into() * (AB::Expr::ONE - local.is_mac.into()));
+        let flag_sum: AB::Expr = local.is_add.into() + local.is_mul.into() + local.is_mac.into();
+        builder.assert_zero(flag_sum.clone() * (flag_sum - AB::Expr::ONE));
+
+        let is_mul_or_mac: AB::Expr = local.is_mul.into() + local.is_mac.into();
+
+        // Effective (b', c') per op: add treats `b` as the addend by
+        // multiplying `a` by the field element 1 and adding `b` in; mul/mac
+        // use `b` as the multiplicand and 0/`c` as the addend.
+        let mut eff_b = [AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO];
+        let mut eff_c = eff_b.clone();
+        for i in 0..BN254_SCALAR_LIMBS {
+            let one_limb = if i == 0 { AB::Expr::ONE } else { AB::Expr::ZERO };
+            eff_b[i] = is_mul_or_mac.clone() * local.b[i].into() + local.is_add.into() * one_limb;
+            eff_c[i] = local.is_add.into() * local.b[i].into() + local.is_mac.into() * local.c[i].into();
+        }
+
+        let modulus = Self::modulus::<AB::Expr>();
+
+        // Unreduced schoolbook product `a * eff_b`, plus `eff_c` folded into
+        // the low limbs, minus `quotient * modulus` and `result`.
+        let mut row_sum: Vec<AB::Expr> = Vec::with_capacity(BN254_PRODUCT_LIMBS);
+        for k in 0..BN254_PRODUCT_LIMBS {
+            let mut term = AB::Expr::ZERO;
+            for i in 0..BN254_SCALAR_LIMBS {
+                if k >= i && k - i < BN254_SCALAR_LIMBS {
+                    let j = k - i;
+                    term = term + local.a[i].into() * eff_b[j].clone();
+                    term = term - local.quotient[i].into() * modulus[j].clone();
+                }
+            }
+            if k < BN254_SCALAR_LIMBS {
+                term = term + eff_c[k].clone() - local.result[k].into();
+            }
+            row_sum.push(term);
+        }
+
+        // Carry propagation: each limb position's running value must be
+        // exactly divisible by 2^32, carrying the quotient into the next
+        // position; the very last position must land on exactly zero, which
+        // is what makes this an integer identity rather than one mod 2^(32*15).
+        let base = AB::Expr::from_canonical_u64(1u64 << 32);
+        let mut carry_prev = AB::Expr::ZERO;
+        for k in 0..BN254_PRODUCT_LIMBS {
+            let t = row_sum[k].clone() + carry_prev.clone();
+            if k < BN254_CARRY_LIMBS {
+                builder.assert_zero(t - local.carry[k].into() * base.clone());
+                carry_prev = local.carry[k].into();
+            } else {
+                builder.assert_zero(t);
+            }
+        }
+
+        builder.assert_zero(local.is_real.into() * (AB::Expr::ONE - local.is_real.into()));
+
+        // result < p: witness `(p - 1) - result` as a borrow-subtracted
+        // 256-bit difference (same shape as the product identity's carry
+        // loop above, but subtracting instead of adding), forcing the final
+        // borrow-out to zero so the subtraction can't have underflowed --
+        // i.e. `result <= p - 1`. Each `lt_diff` limb is range-checked as a
+        // genuine 32-bit value via `Bus::RangeCheck16` in `sends` below, the
+        // same way `MemoryChip`/`RegisterChip` range-check their ordering
+        // diffs, closing the gap the old TODO here left open.
+        let modulus_minus_one = Self::modulus_minus_one::<AB::Expr>();
+        let mut borrow_in = AB::Expr::ZERO;
+        for k in 0..BN254_SCALAR_LIMBS {
+            let t = modulus_minus_one[k].clone() - local.result[k].into() - borrow_in;
+            if k < BN254_CARRY_LIMBS {
+                builder.when(local.is_real).assert_bool(local.lt_borrow[k]);
+                builder
+                    .when(local.is_real)
+                    .assert_eq(local.lt_diff[k], t + local.lt_borrow[k].into() * base.clone());
+                borrow_in = local.lt_borrow[k].into();
+            } else {
+                builder.when(local.is_real).assert_eq(local.lt_diff[k], t);
+            }
+
+            let two_pow_16 = AB::Expr::from_canonical_u32(1 << range::RANGE_CHECK_BITS);
+            let diff_composed: AB::Expr =
+                local.lt_diff_lo[k].into() + two_pow_16 * local.lt_diff_hi[k].into();
+            builder
+                .when(local.is_real)
+                .assert_eq(local.lt_diff[k], diff_composed);
+        }
+    }
+}
+
+impl Bn254ScalarChip {
+    /// The interaction bus tuples this row sends: the 16-bit halves of
+    /// every `lt_diff` limb, range-checking `result < p` (see `eval`).
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &Bn254ScalarColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        let mut out = Vec::with_capacity(BN254_SCALAR_LIMBS * 2);
+        for k in 0..BN254_SCALAR_LIMBS {
+            out.push(builder.send(
+                Bus::RangeCheck16,
+                vec![local.lt_diff_lo[k].into()],
+                local.is_real.into(),
+            ));
+            out.push(builder.send(
+                Bus::RangeCheck16,
+                vec![local.lt_diff_hi[k].into()],
+                local.is_real.into(),
+            ));
+        }
+        out
+    }
+
+    /// Generate trace for bn254 scalar syscalls
+    pub fn generate_trace<F: Field>(&self, syscalls: &[SyscallRecord]) -> RowMajorMatrix<F> {
+        let calls: Vec<_> = syscalls
+            .iter()
+            .filter(|s| {
+                s.code == SyscallCode::Bn254ScalarAdd as u32
+                    || s.code == SyscallCode::Bn254ScalarMul as u32
+                    || s.code == SyscallCode::Bn254ScalarMac as u32
+            })
+            .collect();
+
+        let trace_len = calls.len().next_power_of_two().max(2);
+        let mut values = vec![F::ZERO; trace_len * Bn254ScalarColumns::<F>::NUM_COLUMNS];
+
+        for (i, record) in calls.iter().enumerate() {
+            let row_offset = i * Bn254ScalarColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; BN254_SCALAR_NUM_COLUMNS] = (&mut values
+                [row_offset..row_offset + Bn254ScalarColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+            let cols: &mut Bn254ScalarColumns<F> = row.borrow_mut();
+
+            cols.cycle = F::from_canonical_u64(record.cycle);
+            cols.nonce = F::from_canonical_usize(i);
+
+            let read_limbs = |words: &[u32], offset: usize| -> [u32; BN254_SCALAR_LIMBS] {
+                let mut out = [0u32; BN254_SCALAR_LIMBS];
+                for (j, slot) in out.iter_mut().enumerate() {
+                    *slot = *words.get(offset + j).unwrap_or(&0);
+                }
+                out
+            };
+            let a = read_limbs(&record.inputs, 0);
+            let b = read_limbs(&record.inputs, BN254_SCALAR_LIMBS);
+            let c = if record.code == SyscallCode::Bn254ScalarMac as u32 {
+                read_limbs(&record.inputs, 2 * BN254_SCALAR_LIMBS)
+            } else {
+                [0u32; BN254_SCALAR_LIMBS]
+            };
+
+            let (eff_b, eff_c) = if record.code == SyscallCode::Bn254ScalarAdd as u32 {
+                cols.is_add = F::ONE;
+                let mut one = [0u32; BN254_SCALAR_LIMBS];
+                one[0] = 1;
+                (one, b)
+            } else if record.code == SyscallCode::Bn254ScalarMul as u32 {
+                cols.is_mul = F::ONE;
+                (b, [0u32; BN254_SCALAR_LIMBS])
+            } else {
+                cols.is_mac = F::ONE;
+                (b, c)
+            };
+
+            for j in 0..BN254_SCALAR_LIMBS {
+                cols.a[j] = F::from_canonical_u32(a[j]);
+                cols.b[j] = F::from_canonical_u32(b[j]);
+                cols.c[j] = F::from_canonical_u32(c[j]);
+            }
+
+            let (quotient, result, carries) = compute_mac_mod_p(&a, &eff_b, &eff_c);
+            for j in 0..BN254_SCALAR_LIMBS {
+                cols.quotient[j] = F::from_canonical_u32(quotient[j]);
+                cols.result[j] = F::from_canonical_u32(result[j]);
+            }
+            for j in 0..BN254_CARRY_LIMBS {
+                cols.carry[j] = F::from_canonical_u64(carries[j]);
+            }
+
+            cols.is_real = F::ONE;
+            let (lt_diff, lt_borrow) = compute_lt_diff(&result);
+            for j in 0..BN254_SCALAR_LIMBS {
+                let (lo, hi) = range::decompose_u32(lt_diff[j]);
+                cols.lt_diff[j] = F::from_canonical_u32(lt_diff[j]);
+                cols.lt_diff_lo[j] = F::from_canonical_u32(lo);
+                cols.lt_diff_hi[j] = F::from_canonical_u32(hi);
+            }
+            for j in 0..BN254_CARRY_LIMBS {
+                cols.lt_borrow[j] = F::from_canonical_u32(lt_borrow[j]);
+            }
+        }
+
+        RowMajorMatrix::new(values, Bn254ScalarColumns::<F>::NUM_COLUMNS)
+    }
+}
+
+/// Native bignum helper used by trace generation: treats `a`, `b`, `c` as
+/// little-endian 32-bit-limb integers, computes `q` and `result` such that
+/// `a*b + c = q*p + result` with `0 <= result < p`, and returns the witness
+/// limbs plus the base-2^32 carries the same way `eval` reconstructs them.
+fn compute_mac_mod_p(
+    a: &[u32; BN254_SCALAR_LIMBS],
+    b: &[u32; BN254_SCALAR_LIMBS],
+    c: &[u32; BN254_SCALAR_LIMBS],
+) -> (
+    [u32; BN254_SCALAR_LIMBS],
+    [u32; BN254_SCALAR_LIMBS],
+    [u64; BN254_CARRY_LIMBS],
+) {
+    let to_big = |limbs: &[u32; BN254_SCALAR_LIMBS]| -> WideUint {
+        WideUint::from_limbs(limbs)
+    };
+    let p = WideUint::from_limbs(&BN254_SCALAR_MODULUS);
+    let a_big = to_big(a);
+    let b_big = to_big(b);
+    let c_big = to_big(c);
+
+    let product = a_big.mul(&b_big).add(&c_big);
+    let (q, result) = product.divmod(&p);
+
+    let q_limbs = q.to_limbs();
+    let result_limbs = result.to_limbs();
+
+    // Reconstruct the limbwise row sums and their carries exactly as `eval`
+    // defines them, so the witness matches the constraint.
+    let modulus = BN254_SCALAR_MODULUS;
+    let mut row_sum = [0i128; BN254_PRODUCT_LIMBS];
+    for k in 0..BN254_PRODUCT_LIMBS {
+        let mut term: i128 = 0;
+        for i in 0..BN254_SCALAR_LIMBS {
+            if k >= i && k - i < BN254_SCALAR_LIMBS {
+                let j = k - i;
+                term += a[i] as i128 * b[j] as i128;
+                term -= q_limbs[i] as i128 * modulus[j] as i128;
+            }
+        }
+        if k < BN254_SCALAR_LIMBS {
+            term += c[k] as i128 - result_limbs[k] as i128;
+        }
+        row_sum[k] = term;
+    }
+
+    let mut carries = [0u64; BN254_CARRY_LIMBS];
+    let mut carry_prev: i128 = 0;
+    for k in 0..BN254_CARRY_LIMBS {
+        let t = row_sum[k] + carry_prev;
+        debug_assert_eq!(t.rem_euclid(1i128 << 32), 0, "limb {k} not divisible by 2^32");
+        let carry = t >> 32;
+        carries[k] = carry as u64;
+        carry_prev = carry;
+    }
+    let last = row_sum[BN254_PRODUCT_LIMBS - 1] + carry_prev;
+    debug_assert_eq!(last, 0, "bn254 mac identity did not close exactly");
+
+    (q_limbs, result_limbs, carries)
+}
+
+/// Native bignum helper computing the witness for the `result < p` check in
+/// `eval`: the limbwise borrow-subtraction `(p - 1) - result`, returning the
+/// difference limbs and the borrow bit out of each of the first
+/// `BN254_CARRY_LIMBS` limbs. `compute_mac_mod_p` already guarantees
+/// `0 <= result < p`, so the final limb's borrow is always zero; the
+/// `debug_assert` below would catch it if that ever stopped being true.
+fn compute_lt_diff(
+    result: &[u32; BN254_SCALAR_LIMBS],
+) -> ([u32; BN254_SCALAR_LIMBS], [u32; BN254_CARRY_LIMBS]) {
+    let mut modulus_minus_one = BN254_SCALAR_MODULUS;
+    modulus_minus_one[0] -= 1;
+
+    let mut diff = [0u32; BN254_SCALAR_LIMBS];
+    let mut borrows = [0u32; BN254_CARRY_LIMBS];
+    let mut borrow_in: i64 = 0;
+    for k in 0..BN254_SCALAR_LIMBS {
+        let t = modulus_minus_one[k] as i64 - result[k] as i64 - borrow_in;
+        if k < BN254_CARRY_LIMBS {
+            let borrow_out = if t < 0 { 1 } else { 0 };
+            diff[k] = (t + borrow_out * (1i64 << 32)) as u32;
+            borrows[k] = borrow_out as u32;
+            borrow_in = borrow_out;
+        } else {
+            debug_assert!(t >= 0, "bn254 result >= modulus, result < p bound violated");
+            diff[k] = t as u32;
+        }
+    }
+    (diff, borrows)
+}
+
+/// Minimal fixed-width (8-limb x 2, i.e. up to 512-bit) unsigned bignum used
+/// only to build the witness during trace generation; this is not part of
+/// the circuit, just native arithmetic to compute `q` and `result`.
+#[derive(Clone)]
+struct WideUint {
+    limbs: Vec<u32>,
+}
+
+impl WideUint {
+    fn from_limbs(limbs: &[u32; BN254_SCALAR_LIMBS]) -> Self {
+        Self { limbs: limbs.to_vec() }
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut out = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &ai) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &bj) in other.limbs.iter().enumerate() {
+                let cur = out[i + j] + ai as u64 * bj as u64 + carry;
+                out[i + j] = cur & 0xFFFF_FFFF;
+                carry = cur >> 32;
+            }
+            out[i + other.limbs.len()] += carry;
+        }
+        Self { limbs: out.into_iter().map(|x| x as u32).collect() }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let n = self.limbs.len().max(other.limbs.len());
+        let mut out = vec![0u32; n + 1];
+        let mut carry = 0u64;
+        for i in 0..n {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64
+                + carry;
+            out[i] = sum as u32;
+            carry = sum >> 32;
+        }
+        out[n] = carry as u32;
+        Self { limbs: out }
+    }
+
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let n = self.limbs.len().max(other.limbs.len());
+        for i in (0..n).rev() {
+            let a = *self.limbs.get(i).unwrap_or(&0);
+            let b = *other.limbs.get(i).unwrap_or(&0);
+            if a != b {
+                return a.cmp(&b);
+            }
+        }
+        std::cmp::Ordering::Equal
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        let n = self.limbs.len();
+        let mut out = vec![0u32; n];
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let a = *self.limbs.get(i).unwrap_or(&0) as i64;
+            let b = *other.limbs.get(i).unwrap_or(&0) as i64;
+            let mut diff = a - b - borrow;
+            if diff < 0 {
+                diff += 1i64 << 32;
+                borrow = 1;
+            } else {
+                borrow = 0;
+            }
+            out[i] = diff as u32;
+        }
+        Self { limbs: out }
+    }
+
+    /// Schoolbook long division against `p`, returning `(quotient, remainder)`.
+    fn divmod(&self, p: &Self) -> (Self, Self) {
+        // This chip only ever divides a <512-bit product by a 254-bit
+        // modulus, so a simple shift-and-subtract division is plenty fast
+        // for trace generation (not part of the circuit).
+        let mut remainder = self.clone();
+        let bits = self.limbs.len() * 32;
+        let mut quotient_bits = vec![false; bits];
+
+        for shift in (0..bits).rev() {
+            let shifted = p.shl(shift);
+            if remainder.cmp(&shifted) != std::cmp::Ordering::Less {
+                remainder = remainder.sub(&shifted);
+                quotient_bits[shift] = true;
+            }
+        }
+
+        let mut q_limbs = vec![0u32; BN254_SCALAR_LIMBS];
+        for (bit_idx, &set) in quotient_bits.iter().enumerate() {
+            if set {
+                q_limbs[bit_idx / 32] |= 1 << (bit_idx % 32);
+            }
+        }
+
+        let mut r_limbs = vec![0u32; BN254_SCALAR_LIMBS];
+        for (i, slot) in r_limbs.iter_mut().enumerate() {
+            *slot = *remainder.limbs.get(i).unwrap_or(&0);
+        }
+
+        (Self { limbs: q_limbs }, Self { limbs: r_limbs })
+    }
+
+    fn shl(&self, bits: usize) -> Self {
+        let limb_shift = bits / 32;
+        let bit_shift = bits % 32;
+        let n = self.limbs.len() + limb_shift + 1;
+        let mut out = vec![0u32; n];
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let idx = i + limb_shift;
+            if bit_shift == 0 {
+                out[idx] |= limb;
+            } else {
+                out[idx] |= limb << bit_shift;
+                if idx + 1 < n {
+                    out[idx + 1] |= (limb as u64 >> (32 - bit_shift)) as u32;
+                }
+            }
+        }
+        Self { limbs: out }
+    }
+
+    fn to_limbs(&self) -> [u32; BN254_SCALAR_LIMBS] {
+        let mut out = [0u32; BN254_SCALAR_LIMBS];
+        for (i, slot) in out.iter_mut().enumerate() {
+            *slot = *self.limbs.get(i).unwrap_or(&0);
+        }
+        out
+    }
+}
@@ -0,0 +1,619 @@
+//! Keccak-256 (SHA3) Chip implementation
+//!
+//! Implements the 24-round Keccak-f[1600] permutation over a bit-decomposed
+//! 1600-bit (25 lanes x 64 bits) state. Because the field is prime, XOR/AND
+//! don't correspond to field operations, so every bit of every lane is kept
+//! as its own {0,1} trace column and theta/rho/pi/chi/iota are expressed as
+//! arithmetic over those bits (`XOR(a,b) = a+b-2ab`, `AND(a,b) = a*b`,
+//! `NOT(a) = 1-a`). One row is emitted per round. The sponge uses a
+//! 136-byte (1088-bit) rate, matching Keccak-256.
+//!
+//! The permutation-chaining constraint only applies between two real rows
+//! of the same block (gated by `is_real`/`is_new_block`, see `eval`); a
+//! separate pair of constraints models the per-block absorb XOR at block
+//! boundaries, and a call's first row and last (real, last-block) row are
+//! bound directly to its recorded input and output digest via
+//! `block_input_bits`/`call_output_bits`, so this chip's claimed digest
+//! can't drift from the bytes it was actually asked to hash.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use super::SyscallChip;
+use crate::trace::{SyscallCode, SyscallRecord};
+
+/// Number of 64-bit lanes in the Keccak-f[1600] state
+pub const KECCAK_NUM_LANES: usize = 25;
+/// Bits per lane
+pub const KECCAK_LANE_BITS: usize = 64;
+/// Number of rounds in Keccak-f[1600]
+pub const KECCAK_ROUNDS: usize = 24;
+/// Rate in bytes for Keccak-256 (1088-bit rate, 512-bit capacity)
+pub const KECCAK_RATE_BYTES: usize = 136;
+/// Digest size in bytes for Keccak-256
+pub const KECCAK_DIGEST_BYTES: usize = 32;
+
+/// Rotation offsets `r[x][y]` used by the rho step.
+const RHO_OFFSETS: [[u32; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+/// Round constants for the iota step, one 64-bit word per round.
+const ROUND_CONSTANTS: [u64; KECCAK_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Index of lane `(x, y)` into the 25-lane state, `x, y` in `0..5`.
+const fn lane(x: usize, y: usize) -> usize {
+    x + 5 * y
+}
+
+/// Number of lanes making up the 136-byte (1088-bit) rate portion of the
+/// state; the remaining `KECCAK_NUM_LANES - KECCAK_RATE_LANES` lanes are the
+/// capacity, never touched by absorption.
+const KECCAK_RATE_LANES: usize = KECCAK_RATE_BYTES / 8;
+/// Number of lanes making up the 32-byte Keccak-256 digest, squeezed from
+/// the front of the rate portion.
+const KECCAK_DIGEST_LANES: usize = KECCAK_DIGEST_BYTES / 8;
+
+/// Keccak trace columns: one row per permutation round.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakColumns<T> {
+    /// Cycle when syscall was invoked
+    pub cycle: T,
+    /// Block index (for multi-block absorbs)
+    pub block_idx: T,
+    /// Round number (0..24)
+    pub round: T,
+    /// Bit-decomposed 1600-bit state: `state_bits[lane][bit]`
+    pub state_bits: [[T; KECCAK_LANE_BITS]; KECCAK_NUM_LANES],
+    /// This round's iota constant, bit-decomposed (zero outside lane (0,0))
+    pub round_constant_bits: [T; KECCAK_LANE_BITS],
+    /// 1 for a genuine trace row, 0 for power-of-two padding. Gates the
+    /// permutation/absorb chaining constraints below so they don't fire
+    /// across call boundaries or into padding.
+    pub is_real: T,
+    /// Witnessed boolean, forced true only when `round == 0` (see `eval`'s
+    /// `is_new_block * round == 0` constraint) -- marks the first round of
+    /// any absorbed block, where the chaining constraint switches from
+    /// "apply the permutation" to "absorb this block's bits".
+    pub is_new_block: T,
+    /// Witnessed boolean, forced true only when `round == 0 && block_idx ==
+    /// 0` -- marks the first round of a call (as opposed to an interior
+    /// block of a multi-block absorb), where the state absorbs directly
+    /// from the all-zero initial state rather than from a previous block's
+    /// permuted output.
+    pub is_new_call: T,
+    /// Witnessed boolean, forced true only when `round == KECCAK_ROUNDS -
+    /// 1` -- marks the last round of a block.
+    pub is_last_round: T,
+    /// Trusted witness marking the last block of a call (not independently
+    /// checked against a per-call block count -- the same kind of
+    /// commitment gap as `round_constant_bits` above). Used alongside
+    /// `is_last_round` to select "finished absorbing, squeeze the digest"
+    /// from "finished this block, absorb the next one".
+    pub is_last_block: T,
+    /// This block's rate-sized (136-byte) input, bit-decomposed
+    /// little-endian per lane. Meaningful only on `is_new_block` rows: it's
+    /// XORed into the state absorbed there (see `eval`).
+    pub block_input_bits: [[T; KECCAK_LANE_BITS]; KECCAK_RATE_LANES],
+    /// This call's recorded output digest, bit-decomposed little-endian per
+    /// lane. Meaningful only where `is_last_round && is_last_block`: bound
+    /// there against the squeeze of this row's permuted state.
+    pub call_output_bits: [[T; KECCAK_LANE_BITS]; KECCAK_DIGEST_LANES],
+    /// Row-unique nonce for this chip's interaction bus receives
+    pub nonce: T,
+}
+
+impl<T: Default + Copy> Default for KeccakColumns<T> {
+    fn default() -> Self {
+        Self {
+            cycle: T::default(),
+            block_idx: T::default(),
+            round: T::default(),
+            state_bits: [[T::default(); KECCAK_LANE_BITS]; KECCAK_NUM_LANES],
+            round_constant_bits: [T::default(); KECCAK_LANE_BITS],
+            is_real: T::default(),
+            is_new_block: T::default(),
+            is_new_call: T::default(),
+            is_last_round: T::default(),
+            is_last_block: T::default(),
+            block_input_bits: [[T::default(); KECCAK_LANE_BITS]; KECCAK_RATE_LANES],
+            call_output_bits: [[T::default(); KECCAK_LANE_BITS]; KECCAK_DIGEST_LANES],
+            nonce: T::default(),
+        }
+    }
+}
+
+/// Number of columns in the Keccak trace
+pub const KECCAK_NUM_COLUMNS: usize = 4
+    + KECCAK_NUM_LANES * KECCAK_LANE_BITS
+    + KECCAK_LANE_BITS
+    + 5
+    + KECCAK_RATE_LANES * KECCAK_LANE_BITS
+    + KECCAK_DIGEST_LANES * KECCAK_LANE_BITS;
+
+impl<T> KeccakColumns<T> {
+    pub const NUM_COLUMNS: usize = KECCAK_NUM_COLUMNS;
+}
+
+impl<T> Borrow<KeccakColumns<T>> for [T; KECCAK_NUM_COLUMNS] {
+    fn borrow(&self) -> &KeccakColumns<T> {
+        unsafe { &*(self.as_ptr() as *const KeccakColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<KeccakColumns<T>> for [T; KECCAK_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut KeccakColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut KeccakColumns<T>) }
+    }
+}
+
+/// Keccak-256 Chip backing `SyscallCode::Keccak256`
+pub struct KeccakChip;
+
+impl Default for KeccakChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeccakChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SyscallChip for KeccakChip {
+    fn syscall_code(&self) -> u32 {
+        SyscallCode::Keccak256 as u32
+    }
+
+    fn constraints_per_call(&self) -> usize {
+        // 1600 boolean bit constraints plus the theta/rho/pi/chi/iota
+        // transition, per round, for 24 rounds, plus the per-block absorb
+        // and per-call input/output binding constraints.
+        KECCAK_NUM_LANES * KECCAK_LANE_BITS * KECCAK_ROUNDS
+    }
+}
+
+impl<F: Field> BaseAir<F> for KeccakChip {
+    fn width(&self) -> usize {
+        KECCAK_NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for KeccakChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; KECCAK_NUM_COLUMNS] =
+            local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; KECCAK_NUM_COLUMNS] =
+            next_slice.deref().try_into().unwrap();
+        let local: &KeccakColumns<AB::Var> = local_arr.borrow();
+        let next: &KeccakColumns<AB::Var> = next_arr.borrow();
+
+        // Every state bit must actually be a bit.
+        for l in 0..KECCAK_NUM_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder.assert_bool(local.state_bits[l][b]);
+            }
+        }
+        for l in 0..KECCAK_RATE_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder.assert_bool(local.block_input_bits[l][b]);
+            }
+        }
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_new_block);
+        builder.assert_bool(local.is_new_call);
+        builder.assert_bool(local.is_last_round);
+        builder.assert_bool(local.is_last_block);
+
+        // `is_new_block`/`is_new_call`/`is_last_round` only need the
+        // soundness direction -- "set ⟹ the round/block_idx it claims" --
+        // since trace generation is what's responsible for actually
+        // setting them on the rows where they're true; a prover lying in
+        // the other direction (claiming false when it should be true) just
+        // makes the constraints below fail to apply where they should,
+        // producing a trace that doesn't match its own claimed
+        // `round`/`block_idx`, which the honest prover never does.
+        builder.assert_zero(local.is_new_block.into() * local.round.into());
+        builder.assert_zero(local.is_new_call.into() * local.round.into());
+        builder.assert_zero(local.is_new_call.into() * local.block_idx.into());
+        builder.assert_zero(
+            local.is_last_round.into()
+                * (local.round.into() - AB::Expr::from_canonical_u32((KECCAK_ROUNDS - 1) as u32)),
+        );
+
+        let state: [[AB::Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] =
+            std::array::from_fn(|l| std::array::from_fn(|b| local.state_bits[l][b].into()));
+        let rc_bits: [AB::Expr; KECCAK_LANE_BITS] =
+            std::array::from_fn(|b| local.round_constant_bits[b].into());
+
+        // `round_constant_bits` is a trusted witness column here; binding
+        // it to the canonical per-round table (rather than an arbitrary
+        // prover choice) is left to the same bytecode/lookup-table style
+        // commitment noted in the Poseidon2 chip.
+        let permuted = apply_round::<AB::Expr>(&state, &rc_bits);
+
+        // Permutation step: applies going into any real, non-absorbing row
+        // (i.e. continuing the current block, not starting a new one).
+        // Gated by `next.is_real` so it doesn't fire into padding, and by
+        // `1 - next.is_new_block` so it doesn't fire across an absorb
+        // boundary, where the state update is an XOR, not a permutation --
+        // the exact completeness bug the ungated version had.
+        let next_continues_block: AB::Expr = AB::Expr::ONE - next.is_new_block.into();
+        for l in 0..KECCAK_NUM_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder
+                    .when(next.is_real)
+                    .when(next_continues_block.clone())
+                    .assert_eq(next.state_bits[l][b].into(), permuted[l][b].clone());
+            }
+        }
+
+        // Inter-block absorb: the next row starts a new block of the same
+        // call (not a new call), so its state XORs this block's permuted
+        // output with the new block's input on the rate lanes, and carries
+        // the capacity lanes through unchanged.
+        let next_new_block_same_call: AB::Expr =
+            next.is_new_block.into() * (AB::Expr::ONE - next.is_new_call.into());
+        for l in 0..KECCAK_RATE_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                let absorbed = xor(&permuted[l][b], &next.block_input_bits[l][b].into());
+                builder
+                    .when(next.is_real)
+                    .when(next_new_block_same_call.clone())
+                    .assert_eq(next.state_bits[l][b].into(), absorbed);
+            }
+        }
+        for l in KECCAK_RATE_LANES..KECCAK_NUM_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder
+                    .when(next.is_real)
+                    .when(next_new_block_same_call.clone())
+                    .assert_eq(next.state_bits[l][b].into(), permuted[l][b].clone());
+            }
+        }
+
+        // Call-start absorb: this row itself starts a call, so its own
+        // state is just the first block's input XORed with the all-zero
+        // initial state -- i.e. the input bits directly on the rate lanes,
+        // zero on the capacity lanes. Row-local, so it applies uniformly to
+        // the very first call in the trace and every call after it (no
+        // `when_first_row` special-casing needed).
+        for l in 0..KECCAK_RATE_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder
+                    .when(local.is_new_call)
+                    .assert_eq(local.state_bits[l][b], local.block_input_bits[l][b]);
+            }
+        }
+        for l in KECCAK_RATE_LANES..KECCAK_NUM_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder
+                    .when(local.is_new_call)
+                    .assert_zero(local.state_bits[l][b]);
+            }
+        }
+
+        // Output binding: once the last block of a call has run its last
+        // round, the squeeze of its permuted state (the digest lanes) must
+        // match this call's recorded output. Uses `permuted` directly
+        // rather than `next.state_bits`, since `when_transition()` vanishes
+        // at the trace's literal last row and a call can legitimately end
+        // there.
+        let squeeze_gate: AB::Expr = local.is_last_round.into() * local.is_last_block.into();
+        for l in 0..KECCAK_DIGEST_LANES {
+            for b in 0..KECCAK_LANE_BITS {
+                builder
+                    .when(squeeze_gate.clone())
+                    .assert_eq(local.call_output_bits[l][b], permuted[l][b].clone());
+            }
+        }
+    }
+}
+
+fn xor<Expr: FieldAlgebra + Clone>(a: &Expr, b: &Expr) -> Expr {
+    a.clone() + b.clone() - a.clone() * b.clone() * Expr::from_canonical_u32(2)
+}
+
+fn and<Expr: FieldAlgebra + Clone>(a: &Expr, b: &Expr) -> Expr {
+    a.clone() * b.clone()
+}
+
+fn not<Expr: FieldAlgebra + Clone>(a: &Expr) -> Expr {
+    Expr::ONE - a.clone()
+}
+
+/// theta + rho + pi + chi + iota for a single round, with `rc_bits` the
+/// round's iota constant, bit-decomposed little-endian.
+fn apply_round<Expr: FieldAlgebra + Clone>(
+    state: &[[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES],
+    rc_bits: &[Expr; KECCAK_LANE_BITS],
+) -> [[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] {
+    // Theta
+    let c: [[Expr; KECCAK_LANE_BITS]; 5] = std::array::from_fn(|x| {
+        std::array::from_fn(|b| {
+            let mut acc = state[lane(x, 0)][b].clone();
+            for y in 1..5 {
+                acc = xor(&acc, &state[lane(x, y)][b]);
+            }
+            acc
+        })
+    });
+
+    let theta_out: [[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] = std::array::from_fn(|l| {
+        let x = l % 5;
+        let y = l / 5;
+        std::array::from_fn(|b| {
+            let c_prev = &c[(x + 4) % 5][b];
+            let rot_bit = (b + KECCAK_LANE_BITS - 1) % KECCAK_LANE_BITS;
+            let c_next_rot = &c[(x + 1) % 5][rot_bit];
+            let d = xor(c_prev, c_next_rot);
+            xor(&state[lane(x, y)][b], &d)
+        })
+    });
+
+    // Rho: rotate each lane left by its fixed offset.
+    let rho_out: [[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] = std::array::from_fn(|l| {
+        let x = l % 5;
+        let y = l / 5;
+        let r = (RHO_OFFSETS[x][y] as usize) % KECCAK_LANE_BITS;
+        std::array::from_fn(|b| theta_out[l][(b + KECCAK_LANE_BITS - r) % KECCAK_LANE_BITS].clone())
+    });
+
+    // Pi: A''[y][2x+3y mod 5] = B[x][y]
+    let mut pi_out: [[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] =
+        std::array::from_fn(|_| std::array::from_fn(|_| Expr::ZERO));
+    for x in 0..5 {
+        for y in 0..5 {
+            let new_x = y;
+            let new_y = (2 * x + 3 * y) % 5;
+            pi_out[lane(new_x, new_y)] = rho_out[lane(x, y)].clone();
+        }
+    }
+
+    // Chi
+    let mut chi_out: [[Expr; KECCAK_LANE_BITS]; KECCAK_NUM_LANES] =
+        std::array::from_fn(|_| std::array::from_fn(|_| Expr::ZERO));
+    for x in 0..5 {
+        for y in 0..5 {
+            chi_out[lane(x, y)] = std::array::from_fn(|b| {
+                let a = &pi_out[lane(x, y)][b];
+                let not_b = not(&pi_out[lane((x + 1) % 5, y)][b]);
+                let c_bit = &pi_out[lane((x + 2) % 5, y)][b];
+                xor(a, &and(&not_b, c_bit))
+            });
+        }
+    }
+
+    // Iota: XOR the round constant into lane (0, 0).
+    for b in 0..KECCAK_LANE_BITS {
+        chi_out[lane(0, 0)][b] = xor(&chi_out[lane(0, 0)][b], &rc_bits[b]);
+    }
+
+    chi_out
+}
+
+impl KeccakChip {
+    /// Generate trace for Keccak256 syscalls
+    pub fn generate_trace<F: Field>(&self, syscalls: &[SyscallRecord]) -> RowMajorMatrix<F> {
+        let calls: Vec<_> = syscalls
+            .iter()
+            .filter(|s| s.code == SyscallCode::Keccak256 as u32)
+            .collect();
+
+        // Each call absorbs one or more rate-sized blocks; precompute the
+        // padded block count up front so every call's rows land at a known
+        // offset.
+        let blocks_per_call = |input_bytes: usize| (input_bytes + KECCAK_RATE_BYTES) / KECCAK_RATE_BYTES;
+        let total_rows: usize = calls
+            .iter()
+            .map(|record| blocks_per_call(record.inputs.len() * 4) * KECCAK_ROUNDS)
+            .sum();
+        let trace_len = total_rows.next_power_of_two().max(2);
+
+        let mut values = vec![F::ZERO; trace_len * KECCAK_NUM_COLUMNS];
+
+        let mut row_idx = 0usize;
+        for record in calls.iter() {
+            let input_bytes: Vec<u8> = record
+                .inputs
+                .iter()
+                .flat_map(|w| w.to_le_bytes())
+                .collect();
+            let blocks = absorb_pad(&input_bytes);
+            let num_blocks = blocks.len();
+
+            let mut lanes = [0u64; KECCAK_NUM_LANES];
+            let mut last_row_idx = row_idx;
+            for (block_idx, block) in blocks.iter().enumerate() {
+                for (l, word) in block.iter().enumerate() {
+                    lanes[l] ^= word;
+                }
+
+                for round in 0..KECCAK_ROUNDS {
+                    let row_offset = row_idx * KECCAK_NUM_COLUMNS;
+                    let row = &mut values[row_offset..row_offset + KECCAK_NUM_COLUMNS];
+                    let row_arr: &mut [F; KECCAK_NUM_COLUMNS] =
+                        row.try_into().unwrap();
+                    let cols: &mut KeccakColumns<F> = row_arr.borrow_mut();
+
+                    cols.cycle = F::from_canonical_u64(record.cycle);
+                    cols.block_idx = F::from_canonical_usize(block_idx);
+                    cols.round = F::from_canonical_usize(round);
+                    cols.nonce = F::from_canonical_usize(row_idx);
+                    cols.is_real = F::ONE;
+                    cols.is_new_block = if round == 0 { F::ONE } else { F::ZERO };
+                    cols.is_new_call = if round == 0 && block_idx == 0 {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    };
+                    cols.is_last_round = if round == KECCAK_ROUNDS - 1 {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    };
+                    cols.is_last_block = if block_idx == num_blocks - 1 {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    };
+                    for l in 0..KECCAK_NUM_LANES {
+                        for b in 0..KECCAK_LANE_BITS {
+                            cols.state_bits[l][b] = if (lanes[l] >> b) & 1 == 1 {
+                                F::ONE
+                            } else {
+                                F::ZERO
+                            };
+                        }
+                    }
+                    if round == 0 {
+                        for l in 0..KECCAK_RATE_LANES {
+                            for b in 0..KECCAK_LANE_BITS {
+                                cols.block_input_bits[l][b] =
+                                    if (block[l] >> b) & 1 == 1 { F::ONE } else { F::ZERO };
+                            }
+                        }
+                    }
+                    let rc = ROUND_CONSTANTS[round];
+                    for b in 0..KECCAK_LANE_BITS {
+                        cols.round_constant_bits[b] =
+                            if (rc >> b) & 1 == 1 { F::ONE } else { F::ZERO };
+                    }
+
+                    lanes = apply_round_u64(&lanes, rc);
+                    last_row_idx = row_idx;
+                    row_idx += 1;
+                }
+            }
+
+            let digest = squeeze(&lanes);
+            debug_assert!(
+                record.outputs.is_empty()
+                    || digest
+                        .chunks(4)
+                        .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+                        .eq(record.outputs.iter().copied()),
+                "keccak trace digest does not match recorded syscall output"
+            );
+
+            let row_offset = last_row_idx * KECCAK_NUM_COLUMNS;
+            let row = &mut values[row_offset..row_offset + KECCAK_NUM_COLUMNS];
+            let row_arr: &mut [F; KECCAK_NUM_COLUMNS] = row.try_into().unwrap();
+            let cols: &mut KeccakColumns<F> = row_arr.borrow_mut();
+            for l in 0..KECCAK_DIGEST_LANES {
+                for b in 0..KECCAK_LANE_BITS {
+                    cols.call_output_bits[l][b] = if (lanes[l] >> b) & 1 == 1 {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    };
+                }
+            }
+        }
+
+        RowMajorMatrix::new(values, KECCAK_NUM_COLUMNS)
+    }
+}
+
+/// Pads `input` with the Keccak `0x01 ... 0x80` multi-rate padding and
+/// splits it into rate-sized blocks of 17 little-endian 64-bit lanes.
+fn absorb_pad(input: &[u8]) -> Vec<[u64; 17]> {
+    let mut padded = input.to_vec();
+    padded.push(0x01);
+    while padded.len() % KECCAK_RATE_BYTES != 0 {
+        padded.push(0x00);
+    }
+    *padded.last_mut().unwrap() |= 0x80;
+
+    padded
+        .chunks(KECCAK_RATE_BYTES)
+        .map(|chunk| std::array::from_fn(|i| u64::from_le_bytes(chunk[i * 8..i * 8 + 8].try_into().unwrap())))
+        .collect()
+}
+
+/// Extracts the 32-byte Keccak-256 digest from the first 4 lanes (256 bits)
+/// of the state after the final permutation.
+fn squeeze(lanes: &[u64; KECCAK_NUM_LANES]) -> [u8; KECCAK_DIGEST_BYTES] {
+    let mut out = [0u8; KECCAK_DIGEST_BYTES];
+    for (i, lane) in lanes.iter().take(4).enumerate() {
+        out[i * 8..i * 8 + 8].copy_from_slice(&lane.to_le_bytes());
+    }
+    out
+}
+
+fn apply_round_u64(state: &[u64; KECCAK_NUM_LANES], rc: u64) -> [u64; KECCAK_NUM_LANES] {
+    let mut c = [0u64; 5];
+    for x in 0..5 {
+        c[x] = state[lane(x, 0)] ^ state[lane(x, 1)] ^ state[lane(x, 2)] ^ state[lane(x, 3)] ^ state[lane(x, 4)];
+    }
+
+    let mut theta = [0u64; KECCAK_NUM_LANES];
+    for x in 0..5 {
+        let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+        for y in 0..5 {
+            theta[lane(x, y)] = state[lane(x, y)] ^ d;
+        }
+    }
+
+    let mut pi = [0u64; KECCAK_NUM_LANES];
+    for x in 0..5 {
+        for y in 0..5 {
+            let rotated = theta[lane(x, y)].rotate_left(RHO_OFFSETS[x][y]);
+            let new_x = y;
+            let new_y = (2 * x + 3 * y) % 5;
+            pi[lane(new_x, new_y)] = rotated;
+        }
+    }
+
+    let mut chi = [0u64; KECCAK_NUM_LANES];
+    for x in 0..5 {
+        for y in 0..5 {
+            chi[lane(x, y)] =
+                pi[lane(x, y)] ^ ((!pi[lane((x + 1) % 5, y)]) & pi[lane((x + 2) % 5, y)]);
+        }
+    }
+
+    chi[lane(0, 0)] ^= rc;
+    chi
+}
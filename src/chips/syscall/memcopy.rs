@@ -0,0 +1,247 @@
+//! Bulk `memcpy` precompile chip
+//!
+//! Programs that copy buffers word-by-word burn one CPU row per word
+//! through `is_load`/`is_store`. The `MemCopy` syscall instead copies `len`
+//! words from a source pointer to a destination pointer in a single
+//! syscall record, and this chip emits one trace row per word moved. Each
+//! row reuses a single `value` column for both the source read and the
+//! destination write, which is what constrains the value to be preserved
+//! across the copy (there's only one witness for it to be inconsistent
+//! with). The copy is tied into the shared memory-consistency argument by
+//! sending the read and the write onto the same `Bus::Memory` interaction
+//! bus that ordinary CPU loads/stores use.
+//!
+//! Assumes (matching how the other syscall chips read `SyscallRecord`)
+//! `inputs = [src_ptr, dst_ptr, len]` and `outputs` holds the `len` words
+//! actually moved, since there's no memory-read helper yet to resolve
+//! `src_ptr` through the memory log at trace-generation time.
+
+use std::borrow::{Borrow, BorrowMut};
+use std::ops::Deref;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{Field, FieldAlgebra};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use super::SyscallChip;
+use crate::chips::interaction::{Bus, Interaction, InteractionBuilder};
+use crate::trace::{SyscallCode, SyscallRecord};
+use crate::WORD_SIZE;
+
+/// memcpy trace columns, one row per word copied
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemCopyColumns<T> {
+    /// Cycle when the syscall was invoked
+    pub cycle: T,
+    /// Address of the word read this row
+    pub src_addr: T,
+    /// Address of the word written this row
+    pub dst_addr: T,
+    /// Index of this word within the current call (0-based)
+    pub word_idx: T,
+    /// The word moved from `src_addr` to `dst_addr`
+    pub value: T,
+    /// 1 if this is the first word of a call, 0 if it continues the
+    /// previous row's call (breaks the address/word-index transition
+    /// constraints at call boundaries)
+    pub is_first_word: T,
+    /// 1 for a genuine copied word, 0 for power-of-two padding. Gates the
+    /// bus sends below so padding rows don't inject spurious `(addr=0,
+    /// value=0)` accesses onto `Bus::Memory`.
+    pub is_real: T,
+    /// Row-unique nonce for this chip's interaction bus sends
+    pub nonce: T,
+}
+
+/// Number of columns in the memcpy trace
+pub const MEMCOPY_NUM_COLUMNS: usize = 8;
+
+impl<T> MemCopyColumns<T> {
+    pub const NUM_COLUMNS: usize = MEMCOPY_NUM_COLUMNS;
+}
+
+impl<T> Borrow<MemCopyColumns<T>> for [T; MEMCOPY_NUM_COLUMNS] {
+    fn borrow(&self) -> &MemCopyColumns<T> {
+        unsafe { &*(self.as_ptr() as *const MemCopyColumns<T>) }
+    }
+}
+
+impl<T> BorrowMut<MemCopyColumns<T>> for [T; MEMCOPY_NUM_COLUMNS] {
+    fn borrow_mut(&mut self) -> &mut MemCopyColumns<T> {
+        unsafe { &mut *(self.as_mut_ptr() as *mut MemCopyColumns<T>) }
+    }
+}
+
+/// memcpy precompile chip
+pub struct MemCopyChip;
+
+impl Default for MemCopyChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemCopyChip {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SyscallChip for MemCopyChip {
+    fn syscall_code(&self) -> u32 {
+        SyscallCode::MemCopy as u32
+    }
+
+    fn constraints_per_call(&self) -> usize {
+        // Dominated by the two interaction-bus sends per word.
+        10
+    }
+}
+
+impl<F: Field> BaseAir<F> for MemCopyChip {
+    fn width(&self) -> usize {
+        MemCopyColumns::<F>::NUM_COLUMNS
+    }
+}
+
+impl<AB: AirBuilder> Air<AB> for MemCopyChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_slice = main.row_slice(0);
+        let next_slice = main.row_slice(1);
+
+        let local_arr: &[AB::Var; MEMCOPY_NUM_COLUMNS] = local_slice.deref().try_into().unwrap();
+        let next_arr: &[AB::Var; MEMCOPY_NUM_COLUMNS] = next_slice.deref().try_into().unwrap();
+        let local: &MemCopyColumns<AB::Var> = local_arr.borrow();
+        let next: &MemCopyColumns<AB::Var> = next_arr.borrow();
+
+        builder.assert_zero(
+            local.is_first_word.into() * (AB::Expr::ONE - local.is_first_word.into()),
+        );
+        builder.assert_zero(local.is_real.into() * (AB::Expr::ONE - local.is_real.into()));
+
+        // The first word of a call starts at word index 0.
+        builder
+            .when(local.is_first_word)
+            .assert_zero(local.word_idx.into());
+
+        // Within a call, addresses advance by one word and the word index
+        // advances by one each row. A call boundary (next row starts a new
+        // call) breaks this chain, so only constrain it when the next row
+        // continues the current call.
+        let word_size = AB::Expr::from_canonical_u32(WORD_SIZE as u32);
+        let continues_call = AB::Expr::ONE - next.is_first_word.into();
+
+        builder
+            .when_transition()
+            .when(continues_call.clone())
+            .assert_eq(next.src_addr, local.src_addr.into() + word_size.clone());
+        builder
+            .when_transition()
+            .when(continues_call.clone())
+            .assert_eq(next.dst_addr, local.dst_addr.into() + word_size);
+        builder
+            .when_transition()
+            .when(continues_call)
+            .assert_eq(next.word_idx, local.word_idx.into() + AB::Expr::ONE);
+    }
+}
+
+impl MemCopyChip {
+    /// The interaction bus tuples this row sends: a memory read at
+    /// `src_addr` and a memory write at `dst_addr`, both carrying the same
+    /// witnessed `value`. Both are gated by `is_real` so the power-of-two
+    /// padding rows (which otherwise look like a trivial all-zero word
+    /// copy) don't send spurious accesses onto `Bus::Memory`.
+    pub fn sends<AB: InteractionBuilder>(
+        &self,
+        builder: &AB,
+        local: &MemCopyColumns<AB::Var>,
+    ) -> Vec<Interaction<AB::Expr>> {
+        vec![
+            builder.send(
+                Bus::Memory,
+                vec![
+                    local.src_addr.into(),
+                    local.value.into(),
+                    AB::Expr::ZERO,
+                    local.cycle.into(),
+                    local.nonce.into(),
+                ],
+                local.is_real.into(),
+            ),
+            builder.send(
+                Bus::Memory,
+                vec![
+                    local.dst_addr.into(),
+                    local.value.into(),
+                    AB::Expr::ONE,
+                    local.cycle.into(),
+                    local.nonce.into(),
+                ],
+                local.is_real.into(),
+            ),
+        ]
+    }
+
+    /// Generate trace for memcpy syscalls, one row per word copied.
+    pub fn generate_trace<F: Field>(&self, syscalls: &[SyscallRecord]) -> RowMajorMatrix<F> {
+        let calls: Vec<_> = syscalls
+            .iter()
+            .filter(|s| s.code == SyscallCode::MemCopy as u32)
+            .collect();
+
+        let total_words: usize = calls
+            .iter()
+            .map(|record| *record.inputs.get(2).unwrap_or(&0) as usize)
+            .sum();
+        let trace_len = total_words.next_power_of_two().max(2);
+
+        let mut values = vec![F::ZERO; trace_len * MemCopyColumns::<F>::NUM_COLUMNS];
+
+        let mut row_idx = 0usize;
+        for record in &calls {
+            let src_ptr = *record.inputs.first().unwrap_or(&0);
+            let dst_ptr = *record.inputs.get(1).unwrap_or(&0);
+            let len = *record.inputs.get(2).unwrap_or(&0) as usize;
+
+            for word in 0..len {
+                let row_offset = row_idx * MemCopyColumns::<F>::NUM_COLUMNS;
+                let row: &mut [F; MEMCOPY_NUM_COLUMNS] = (&mut values
+                    [row_offset..row_offset + MemCopyColumns::<F>::NUM_COLUMNS])
+                    .try_into()
+                    .unwrap();
+                let cols: &mut MemCopyColumns<F> = row.borrow_mut();
+
+                cols.cycle = F::from_canonical_u64(record.cycle);
+                cols.src_addr = F::from_canonical_u32(src_ptr + (word * WORD_SIZE) as u32);
+                cols.dst_addr = F::from_canonical_u32(dst_ptr + (word * WORD_SIZE) as u32);
+                cols.word_idx = F::from_canonical_usize(word);
+                cols.value = F::from_canonical_u32(*record.outputs.get(word).unwrap_or(&0));
+                cols.is_first_word = if word == 0 { F::ONE } else { F::ZERO };
+                cols.is_real = F::ONE;
+                cols.nonce = F::from_canonical_usize(row_idx);
+
+                row_idx += 1;
+            }
+        }
+
+        // Padding rows each look like a trivial one-word call (word_idx = 0,
+        // all-zero addresses/value) so the transition constraints, which
+        // only hold within a call, don't chain across them.
+        for i in row_idx..trace_len {
+            let row_offset = i * MemCopyColumns::<F>::NUM_COLUMNS;
+            let row: &mut [F; MEMCOPY_NUM_COLUMNS] = (&mut values
+                [row_offset..row_offset + MemCopyColumns::<F>::NUM_COLUMNS])
+                .try_into()
+                .unwrap();
+            let cols: &mut MemCopyColumns<F> = row.borrow_mut();
+            cols.is_first_word = F::ONE;
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        RowMajorMatrix::new(values, MemCopyColumns::<F>::NUM_COLUMNS)
+    }
+}
@@ -3,10 +3,19 @@
 //! Dedicated chips for expensive cryptographic operations:
 //! - Poseidon2: Hash function (~200 constraints per hash)
 //! - SHA256: Hash function (~20,000 constraints per block)
+//! - Keccak256: Hash function (bit-sliced, ~1600 constraints per round)
+//! - bn254 scalar field: add/mul/mac precompile (~150 constraints per call)
+//! - memcpy: bulk memory move precompile (~10 constraints per word)
 
-mod poseidon;
+mod bn254;
+mod keccak;
+mod memcopy;
+pub(crate) mod poseidon;
 mod sha256;
 
+pub use bn254::Bn254ScalarChip;
+pub use keccak::KeccakChip;
+pub use memcopy::MemCopyChip;
 pub use poseidon::Poseidon2Chip;
 pub use sha256::Sha256Chip;
 
@@ -57,6 +57,8 @@ pub struct Sha256Columns<T> {
     pub temp1: T,
     /// temp2 = sigma0 + maj
     pub temp2: T,
+    /// Row-unique nonce for this chip's interaction bus receives
+    pub nonce: T,
 }
 
 impl<T: Default + Copy> Default for Sha256Columns<T> {
@@ -81,6 +83,7 @@ impl<T: Default + Copy> Default for Sha256Columns<T> {
             sigma1: T::default(),
             temp1: T::default(),
             temp2: T::default(),
+            nonce: T::default(),
         }
     }
 }
@@ -0,0 +1,74 @@
+//! Fiat-Shamir transcript for deriving cross-chip bus challenges.
+//!
+//! Every LogUp/grand-product argument in this prover (the cross-chip
+//! interaction bus in `chips::interaction`, `MemoryChip`'s and
+//! `RegisterChip`'s own sorted/exec permutation arguments) needs challenges
+//! that a prover cannot have predicted before committing to its traces. A
+//! bare hand-picked constant fails that even if it "looks random": anyone
+//! reading the source knows it in advance. This module squeezes challenges
+//! out of a sponge built on the same width-16 Poseidon2 permutation
+//! `chips::syscall::poseidon` already implements (see `poseidon::permute`),
+//! so at least the values are the output of a real cryptographic
+//! permutation over an explicit domain separator rather than arbitrary hex.
+//!
+//! TODO: no commitment scheme exists yet in this prover (`proof`/`prover`
+//! aren't implemented), so there's nothing to absorb except the domain
+//! separator below -- the challenges this module produces are therefore
+//! still fixed across runs, exactly like the constants they replace. Once a
+//! real prover module exists, it must call `Transcript::absorb_commitment`
+//! with each chip's trace commitment *before* any `draw_ext_challenge`, or
+//! this remains forgeable the same way the old hard-coded constants were.
+
+use p3_field::{AbstractExtensionField, FieldAlgebra};
+
+use crate::chips::syscall::poseidon::{permute, POSEIDON2_WIDTH};
+use crate::EF;
+
+/// A duplex sponge over the width-16 Poseidon2 permutation: `absorb_*` mixes
+/// a value into the state, `draw_*` permutes and reads challenges back out.
+pub struct Transcript {
+    state: [u32; POSEIDON2_WIDTH],
+}
+
+impl Transcript {
+    /// Start a new transcript, absorbing a domain separator so different
+    /// uses of this sponge (e.g. the cross-chip bus vs. some future
+    /// per-chip argument) can never collide on the same challenges.
+    pub fn new(domain: &[u8]) -> Self {
+        let mut t = Self {
+            state: [0u32; POSEIDON2_WIDTH],
+        };
+        for chunk in domain.chunks(4) {
+            let mut bytes = [0u8; 4];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            t.absorb_u32(u32::from_le_bytes(bytes));
+        }
+        t
+    }
+
+    /// Mix one `u32` (reduced mod the Baby Bear prime) into the sponge
+    /// state and permute.
+    pub fn absorb_u32(&mut self, v: u32) {
+        self.state[0] = ((self.state[0] as u64 + (v as u64 % crate::BABY_BEAR_PRIME as u64))
+            % crate::BABY_BEAR_PRIME as u64) as u32;
+        self.state = permute(self.state);
+    }
+
+    /// Mix a commitment (given as raw base-field limbs) into the sponge,
+    /// once a real commitment scheme produces one. Unused today -- see this
+    /// module's TODO -- but the hook a real prover module needs.
+    pub fn absorb_commitment(&mut self, limbs: &[u32]) {
+        for &limb in limbs {
+            self.absorb_u32(limb);
+        }
+    }
+
+    /// Squeeze a degree-4 extension-field challenge: permute the state and
+    /// read the first four lanes back as `crate::EF` coordinates.
+    pub fn draw_ext_challenge(&mut self) -> EF {
+        self.state = permute(self.state);
+        let coords: [crate::F; 4] =
+            std::array::from_fn(|i| crate::F::from_canonical_u32(self.state[i] % crate::BABY_BEAR_PRIME));
+        EF::from_base_slice(&coords)
+    }
+}
@@ -24,10 +24,18 @@ pub use trace::ExecutionTrace;
 pub use verifier::Verifier;
 
 use p3_baby_bear::BabyBear;
+use p3_field::extension::BinomialExtensionField;
 
 /// The field type used throughout the prover (Baby Bear: p = 2^31 - 2^27 + 1)
 pub type F = BabyBear;
 
+/// Degree-4 extension of Baby Bear (`x^4 - 11`), used for Fiat-Shamir
+/// challenges and the running sums of every permutation/LogUp argument in
+/// the prover (see `chips::ext`). `F` alone is only ~31 bits -- far too
+/// small for these arguments to be sound over a single base-field
+/// challenge -- while `EF` brings the soundness error down to ~2^-124.
+pub type EF = BinomialExtensionField<F, 4>;
+
 /// Baby Bear prime: 2^31 - 2^27 + 1 = 2013265921
 pub const BABY_BEAR_PRIME: u32 = 2013265921;
 
@@ -0,0 +1,358 @@
+//! The ZK IR machine: wires the per-chip AIRs together and closes the
+//! cross-chip LogUp bus between them.
+//!
+//! Each chip's `Air::eval` only ever sees its own trace, so an argument that
+//! spans two chips -- "every memory access the CPU chip claims to have made
+//! is exactly the multiset of accesses the memory chip received" -- can't be
+//! a single polynomial constraint. Instead each chip accumulates a signed
+//! running sum (`phi`) of `multiplicity / fingerprint` over its own rows,
+//! with the division rewritten via a witnessed inverse so the update stays
+//! polynomial (see `CpuColumns::mem_bus_phi` and `MemoryColumns::mem_bus_phi`
+//! for the per-chip half of the argument). `ZkIrMachine` is responsible for
+//! the other half: checking that every chip's final `phi` sums to zero
+//! across the whole proof, which is the actual LogUp closure check and has
+//! to live here because it's the only place that sees both traces at once.
+
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+
+use crate::chips::alu::AluColumns;
+use crate::chips::cpu::CpuColumns;
+use crate::chips::fpu::FpuColumns;
+use crate::chips::memory::MemoryColumns;
+use crate::chips::program::ProgramColumns;
+use crate::chips::range::RangeCheckColumns;
+use crate::chips::register::RegisterColumns;
+use crate::chips::shift::ShiftPowColumns;
+use crate::chips::{
+    AluChip, CpuChip, FpuChip, MemoryChip, ProgramChip, RangeCheckChip, RegisterChip, ShiftPowChip,
+};
+use crate::trace::ExecutionTrace;
+
+/// Selects which field a `ZkIrMachine` draws its interaction-bus and
+/// permutation challenges from.
+///
+/// TODO: only the type-level selection lives here today -- every chip's bus
+/// and permutation columns (`chips::ext`, `CpuColumns::mem_bus_phi`,
+/// `MemoryColumns::acc_sorted`/`acc_exec`/`mem_bus_phi`) are still hard-coded
+/// to 4 base-field limbs per accumulator, so picking `BaseFieldChallenge`
+/// here doesn't yet change what the chips themselves constrain. Making the
+/// column layout itself generic over the extension degree is a bigger
+/// change than adding the selection point.
+pub trait ChallengeField {
+    /// Degree of the extension this variant draws challenges from.
+    const EXTENSION_DEGREE: usize;
+}
+
+/// Draw challenges from `crate::EF`, the quartic extension -- the default,
+/// sound choice for real proofs (~2^-124 soundness error).
+pub struct ExtensionChallenge;
+
+impl ChallengeField for ExtensionChallenge {
+    const EXTENSION_DEGREE: usize = 4;
+}
+
+/// Draw challenges from the base field directly -- far weaker soundness
+/// (~2^-31), useful only to cut cost in tests that don't care about it.
+pub struct BaseFieldChallenge;
+
+impl ChallengeField for BaseFieldChallenge {
+    const EXTENSION_DEGREE: usize = 1;
+}
+
+/// Top-level multi-chip machine for the ZK IR prover, generic over which
+/// field its cross-chip arguments draw challenges from (see
+/// `ChallengeField`).
+pub struct ZkIrMachine<C: ChallengeField = ExtensionChallenge> {
+    pub cpu: CpuChip,
+    pub memory: MemoryChip,
+    pub register: RegisterChip,
+    pub range: RangeCheckChip,
+    pub program: ProgramChip,
+    pub shift: ShiftPowChip,
+    pub alu: AluChip,
+    pub fpu: FpuChip,
+    _challenge: PhantomData<C>,
+}
+
+impl<C: ChallengeField> Default for ZkIrMachine<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: ChallengeField> ZkIrMachine<C> {
+    pub fn new() -> Self {
+        Self {
+            cpu: CpuChip::new(),
+            memory: MemoryChip::new(),
+            register: RegisterChip::new(),
+            range: RangeCheckChip::new(),
+            program: ProgramChip::new(),
+            shift: ShiftPowChip::new(),
+            alu: AluChip::new(),
+            fpu: FpuChip::new(),
+            _challenge: PhantomData,
+        }
+    }
+
+    /// Generate every chip's trace from a single execution trace. Fixed to
+    /// `crate::F`, like the per-chip trace generators it calls: the bus
+    /// columns they populate go through `crate::EF`.
+    ///
+    /// The range-check, program, and shift-power traces are generated last
+    /// because their `multiplicity` columns depend on tallies produced while
+    /// generating the memory, CPU, and ALU traces (see
+    /// `MemoryChip::generate_trace`'s, `CpuChip::generate_trace`'s, and
+    /// `AluChip::generate_trace`'s extra return values) -- today's only
+    /// senders wired into this machine for `Bus::RangeCheck16`,
+    /// `Bus::Program`, and `Bus::ShiftPow` respectively. The range-check
+    /// tally is the element-wise sum of all three chips' sends, since the
+    /// memory chip's address/cycle gaps, the CPU's shift limbs, and the
+    /// ALU's sum/diff limbs all share that one table.
+    ///
+    /// Returns the ALU trace as a 7th element and the FPU trace as an 8th,
+    /// alongside the original six.
+    pub fn generate_traces(
+        &self,
+        trace: &ExecutionTrace,
+    ) -> (
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+        RowMajorMatrix<crate::F>,
+    ) {
+        let (cpu_trace, program_multiplicities, cpu_range_multiplicities, shift_multiplicities) =
+            self.cpu.generate_trace(trace);
+        let (memory_trace, memory_range_multiplicities) = self.memory.generate_trace(trace);
+        let register_trace = self.register.generate_trace(trace);
+        let (alu_trace, alu_range_multiplicities) = self.alu.generate_trace(trace);
+        let fpu_trace = self.fpu.generate_trace(trace);
+
+        let mut range_multiplicities = memory_range_multiplicities;
+        for (total, cpu_count) in range_multiplicities.iter_mut().zip(cpu_range_multiplicities.iter()) {
+            *total += cpu_count;
+        }
+        for (total, alu_count) in range_multiplicities.iter_mut().zip(alu_range_multiplicities.iter()) {
+            *total += alu_count;
+        }
+
+        let range_trace = self.range.generate_trace(&range_multiplicities);
+        let program_trace = self.program.generate_trace(&trace.program, &program_multiplicities);
+        let shift_trace = self.shift.generate_trace(&shift_multiplicities);
+        (
+            cpu_trace,
+            memory_trace,
+            register_trace,
+            range_trace,
+            program_trace,
+            shift_trace,
+            alu_trace,
+            fpu_trace,
+        )
+    }
+
+    /// Check that the CPU chip's and memory chip's memory-bus `phi` columns
+    /// -- each a degree-4 extension element, see `chips::ext` -- sum to zero
+    /// at the end of their respective traces -- the LogUp closure check for
+    /// the bus described in `chips::interaction`.
+    ///
+    /// Every other piece of the argument (that each chip's own `phi` really
+    /// is the claimed running sum, not just any value) is already enforced
+    /// per-chip in `Air::eval`; this is the one remaining cross-table
+    /// equality that has no single AIR to live in.
+    pub fn check_memory_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        memory_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.mem_bus_phi,
+        );
+        let memory_phi = Self::final_column::<
+            MemoryColumns<crate::F>,
+            { MemoryColumns::<crate::F>::NUM_COLUMNS },
+        >(memory_trace, |cols| cols.mem_bus_phi);
+
+        (0..4).all(|i| cpu_phi[i] + memory_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the CPU chip's and register chip's register-bus `phi`
+    /// columns sum to zero at the end of their respective traces -- the
+    /// LogUp closure check for `CpuColumns::reg_bus_phi` /
+    /// `RegisterColumns::reg_bus_phi`, the register analogue of
+    /// `check_memory_bus_closure` above.
+    pub fn check_register_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        register_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.reg_bus_phi,
+        );
+        let register_phi = Self::final_column::<
+            RegisterColumns<crate::F>,
+            { RegisterColumns::<crate::F>::NUM_COLUMNS },
+        >(register_trace, |cols| cols.reg_bus_phi);
+
+        (0..4).all(|i| cpu_phi[i] + register_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the memory chip's, CPU chip's, ALU chip's, and
+    /// range-check chip's `Bus::RangeCheck16` `phi` columns sum to zero --
+    /// the LogUp closure check for `MemoryColumns::range_bus_phi` /
+    /// `CpuColumns::range_bus_phi` / `AluColumns::range_bus_phi` /
+    /// `RangeCheckColumns::phi`. This is what makes the address/cycle
+    /// ordering `MemoryChip::eval` proves via `addr_diff_lo/hi`/
+    /// `cycle_diff_lo/hi`, the shift limbs `CpuChip::eval` proves via
+    /// `shift_remainder_lo/hi`/`shift_overflow_lo/hi`, and the ADD/SUB
+    /// result limbs `AluChip::eval` proves via `rd_lo`/`rd_hi`, actually
+    /// mean "a valid 32-bit gap/limb" rather than an arbitrary pair of
+    /// witnessed values: those limbs are only proven to fit in 16 bits
+    /// each if they're real entries in `RangeCheckChip`'s table, which is
+    /// exactly what this closure checks.
+    pub fn check_range_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        memory_trace: &RowMajorMatrix<crate::F>,
+        range_trace: &RowMajorMatrix<crate::F>,
+        alu_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.range_bus_phi,
+        );
+        let memory_phi = Self::final_column::<
+            MemoryColumns<crate::F>,
+            { MemoryColumns::<crate::F>::NUM_COLUMNS },
+        >(memory_trace, |cols| cols.range_bus_phi);
+        let range_phi = Self::final_column::<
+            RangeCheckColumns<crate::F>,
+            { RangeCheckColumns::<crate::F>::NUM_COLUMNS },
+        >(range_trace, |cols| cols.phi);
+        let alu_phi = Self::final_column::<AluColumns<crate::F>, { AluColumns::<crate::F>::NUM_COLUMNS }>(
+            alu_trace,
+            |cols| cols.range_bus_phi,
+        );
+
+        (0..4).all(|i| cpu_phi[i] + memory_phi[i] + range_phi[i] + alu_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the CPU chip's and ALU chip's `Bus::Alu` `phi` columns sum
+    /// to zero -- the LogUp closure check for `CpuColumns::alu_bus_phi` /
+    /// `AluColumns::phi`. This is what actually binds `rd_val` to
+    /// `alu_op(rs1_val, rs2_val)` for ALU rows; without it `CpuChip::sends`
+    /// onto `Bus::Alu` is unconstrained by anything on the receive side.
+    pub fn check_alu_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        alu_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.alu_bus_phi,
+        );
+        let alu_phi = Self::final_column::<AluColumns<crate::F>, { AluColumns::<crate::F>::NUM_COLUMNS }>(
+            alu_trace,
+            |cols| cols.phi,
+        );
+
+        (0..4).all(|i| cpu_phi[i] + alu_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the CPU chip's and FPU chip's `Bus::Fpu` `phi` columns sum
+    /// to zero -- the LogUp closure check for `CpuColumns::fpu_bus_phi` /
+    /// `chips::fpu::FpuColumns::phi`. This is what actually binds `rd_val`
+    /// to `chips::fpu::FpuChip`'s recomputed result for RV32F rows; without
+    /// it `CpuChip::sends` onto `Bus::Fpu` is unconstrained by anything
+    /// on the receive side.
+    pub fn check_fpu_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        fpu_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.fpu_bus_phi,
+        );
+        let fpu_phi = Self::final_column::<FpuColumns<crate::F>, { FpuColumns::<crate::F>::NUM_COLUMNS }>(
+            fpu_trace,
+            |cols| cols.phi,
+        );
+
+        (0..4).all(|i| cpu_phi[i] + fpu_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the CPU chip's and program chip's `Bus::Program` `phi`
+    /// columns sum to zero -- the LogUp closure check for
+    /// `CpuColumns::program_bus_phi` / `ProgramColumns::phi`. This is what
+    /// makes the CPU's decoded `opcode`/`rs1`/`rs2`/`rd`/`imm`/`funct` on
+    /// every row mean "fetched from a real committed program instruction at
+    /// `pc`" rather than whatever a prover invents.
+    pub fn check_program_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        program_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.program_bus_phi,
+        );
+        let program_phi = Self::final_column::<
+            ProgramColumns<crate::F>,
+            { ProgramColumns::<crate::F>::NUM_COLUMNS },
+        >(program_trace, |cols| cols.phi);
+
+        (0..4).all(|i| cpu_phi[i] + program_phi[i] == crate::F::ZERO)
+    }
+
+    /// Check that the CPU chip's and shift-power chip's `Bus::ShiftPow`
+    /// `phi` columns sum to zero -- the LogUp closure check for
+    /// `CpuColumns::shift_bus_phi` / `ShiftPowColumns::phi`. This is what
+    /// makes `CpuColumns::shift_pow` actually mean `2^shift_amount` instead
+    /// of an arbitrary witnessed value the SLL/SRL/SRA constraints in
+    /// `chips::cpu::air` build on top of.
+    pub fn check_shift_bus_closure(
+        &self,
+        cpu_trace: &RowMajorMatrix<crate::F>,
+        shift_trace: &RowMajorMatrix<crate::F>,
+    ) -> bool {
+        let cpu_phi = Self::final_column::<CpuColumns<crate::F>, { CpuColumns::<crate::F>::NUM_COLUMNS }>(
+            cpu_trace,
+            |cols| cols.shift_bus_phi,
+        );
+        let shift_phi = Self::final_column::<
+            ShiftPowColumns<crate::F>,
+            { ShiftPowColumns::<crate::F>::NUM_COLUMNS },
+        >(shift_trace, |cols| cols.phi);
+
+        (0..4).all(|i| cpu_phi[i] + shift_phi[i] == crate::F::ZERO)
+    }
+
+    /// Read a single (possibly extension-field, i.e. `[F; 4]`) column out of
+    /// a chip's last trace row via the same `[F; N]` <-> typed-columns
+    /// transmute every chip's `Air::eval` uses.
+    fn final_column<Cols, const N: usize>(
+        trace: &RowMajorMatrix<crate::F>,
+        get: impl FnOnce(&Cols) -> [crate::F; 4],
+    ) -> [crate::F; 4]
+    where
+        [crate::F; N]: Borrow<Cols>,
+    {
+        let last = trace.height() - 1;
+        let row = trace.row_slice(last);
+        let row_arr: &[crate::F; N] = row.deref().try_into().unwrap();
+        get(row_arr.borrow())
+    }
+}
@@ -30,6 +30,50 @@ pub struct Step {
     pub registers: [u32; NUM_REGISTERS],
 }
 
+/// A single committed program instruction, keyed by `pc`.
+///
+/// Distinct from `Step`: a `Step` is one dynamic visit to a `pc` during
+/// execution (a loop body revisits the same `pc` many times), while
+/// `ProgramEntry` is the static bytecode laid out once, in `pc` order, that
+/// `chips::program::ProgramChip` commits as a lookup table (see
+/// `ExecutionTrace::program`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProgramEntry {
+    /// Program counter
+    pub pc: u32,
+    /// Opcode
+    pub opcode: u8,
+    /// Destination register
+    pub rd: u8,
+    /// Source register 1
+    pub rs1: u8,
+    /// Source register 2
+    pub rs2: u8,
+    /// Immediate value
+    pub imm: i32,
+    /// Function code (funct3 + funct7)
+    pub funct: u8,
+}
+
+/// A register-file access record, analogous to `MemoryAccess` (see
+/// `chips::register::RegisterChip`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegisterAccess {
+    /// Register index (`0..NUM_REGISTERS`)
+    pub reg_index: u32,
+    /// Monotonic access sequence number, not the raw CPU cycle: a single
+    /// step can touch up to three registers (an `rs1` read, an `rs2` read,
+    /// and an `rd` write), so this is `cycle * 3 + slot` (0 for the `rs1`
+    /// read, 1 for the `rs2` read, 2 for the `rd` write), keeping accesses
+    /// to the same register in the strict order `RegisterChip` needs, the
+    /// same role `MemoryAccess::cycle` plays for memory.
+    pub cycle: u64,
+    /// Value read or written
+    pub value: u32,
+    /// True if write, false if read
+    pub is_write: bool,
+}
+
 /// A memory access record
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MemoryAccess {
@@ -68,6 +112,10 @@ pub enum SyscallCode {
     Ed25519Verify = 0x11,
     BigintAdd = 0x20,
     BigintMul = 0x21,
+    Bn254ScalarAdd = 0x30,
+    Bn254ScalarMul = 0x31,
+    Bn254ScalarMac = 0x32,
+    MemCopy = 0x40,
 }
 
 /// Complete execution trace
@@ -75,6 +123,10 @@ pub enum SyscallCode {
 pub struct ExecutionTrace {
     /// Program bytecode hash
     pub program_hash: [u8; 32],
+    /// The committed program, in `pc` order -- the static lookup table
+    /// `chips::program::ProgramChip` enforces every executed `Step` was
+    /// fetched from (see `ProgramEntry`).
+    pub program: Vec<ProgramEntry>,
     /// Public inputs
     pub inputs: Vec<u32>,
     /// Public outputs
@@ -83,6 +135,11 @@ pub struct ExecutionTrace {
     pub steps: Vec<Step>,
     /// Memory accesses (in execution order)
     pub memory_log: Vec<MemoryAccess>,
+    /// Register-file accesses (in execution order) -- the `rs1`/`rs2` reads
+    /// and `rd` write of every step, consumed by
+    /// `chips::register::RegisterChip` the same way `memory_log` is
+    /// consumed by `chips::memory::MemoryChip`.
+    pub register_log: Vec<RegisterAccess>,
     /// Syscall records
     pub syscalls: Vec<SyscallRecord>,
 }
@@ -92,10 +149,12 @@ impl ExecutionTrace {
     pub fn new(program_hash: [u8; 32]) -> Self {
         Self {
             program_hash,
+            program: Vec::new(),
             inputs: Vec::new(),
             outputs: Vec::new(),
             steps: Vec::new(),
             memory_log: Vec::new(),
+            register_log: Vec::new(),
             syscalls: Vec::new(),
         }
     }
@@ -128,6 +187,15 @@ impl ExecutionTrace {
         sorted
     }
 
+    /// Get register accesses sorted by (reg_index, cycle) for the register chip
+    pub fn sorted_register_log(&self) -> Vec<RegisterAccess> {
+        let mut sorted = self.register_log.clone();
+        sorted.sort_by(|a, b| {
+            a.reg_index.cmp(&b.reg_index).then(a.cycle.cmp(&b.cycle))
+        });
+        sorted
+    }
+
     /// Get syscalls by type
     pub fn syscalls_by_code(&self, code: SyscallCode) -> Vec<&SyscallRecord> {
         self.syscalls